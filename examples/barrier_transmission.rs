@@ -0,0 +1,69 @@
+use std::fs;
+
+use sim_quantum::prelude::*;
+use plotters::prelude::*;
+
+fn main() {
+    // Compute the tunneling spectrum through the central barrier of the double well.
+    let config = ScatteringConfig {
+        x_min: -0.5,
+        x_max: 0.5,
+        step_size: 1e-4,
+        potential: SquareWell {
+            width: 0.2,
+            depth: -100.0,
+        },
+    };
+    let solver = ScatteringSolver::new(&config);
+    let energies: Vec<f64> = (1..=600).map(|i| 0.5 * i as f64).collect();
+    let spectrum = solver
+        .spectrum(&energies)
+        .expect("Failed to compute the scattering coefficients");
+
+    // Plot the data
+    fs::create_dir_all("img").expect("Failed to create image directory");
+    let root_area =
+        BitMapBackend::new("img/barrier_transmission.png", (1280, 720)).into_drawing_area();
+    root_area.fill(&WHITE).unwrap();
+
+    let mut ctx = ChartBuilder::on(&root_area)
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .caption(
+            "Transmission through the double well barrier",
+            ("sans-serif", 40),
+        )
+        .build_cartesian_2d(0.0..300.0, 0.0..1.05)
+        .unwrap();
+
+    ctx.configure_mesh()
+        .x_desc("E")
+        .y_desc("T, R")
+        .axis_desc_style(("sans-serif", 20))
+        .draw()
+        .unwrap();
+
+    ctx.draw_series(LineSeries::new(
+        spectrum.iter().map(|result| (result.energy, result.transmission)),
+        &BLUE,
+    ))
+    .unwrap()
+    .label("T")
+    .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &BLUE));
+
+    ctx.draw_series(LineSeries::new(
+        spectrum.iter().map(|result| (result.energy, result.reflection)),
+        &RED,
+    ))
+    .unwrap()
+    .label("R")
+    .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &RED));
+
+    ctx.configure_series_labels()
+        .position(SeriesLabelPosition::MiddleRight)
+        .label_font(("sans-serif", 20))
+        .background_style(&WHITE.mix(0.8))
+        .border_style(&BLACK)
+        .draw()
+        .unwrap();
+}
@@ -13,12 +13,15 @@ fn main() {
         initial_energy: 21.0,
         initial_energy_step_size: 1.0,
         energy_step_size_cutoff: 0.001,
+        max_iterations: 10000,
         potential: double_well_potential,
         using_numerov: true,
-        guarding_scale_factor: true
+        guarding_scale_factor: true,
+        quantum_number: None,
+        quadrature: Quadrature::Trapezoidal,
     };
     let mut solver = MatchingSolver::new(&config);
-    solver.solve();
+    solver.solve().expect("Failed to solve the Schrodinger equation");
 
     // Plot the data
     fs::create_dir_all("img").expect("Failed to create image directory");
@@ -0,0 +1,73 @@
+use std::fs;
+
+use sim_quantum::prelude::*;
+use plotters::prelude::*;
+
+fn main() {
+    // Propagate a wavepacket starting in the left well of the double well potential.
+    // The walls are very high, so the time step has to be small to conserve energy.
+    let config = SplitOperatorConfig {
+        x_min: -1.5,
+        x_max: 1.5,
+        step_size: 3.0 / 512.0,
+        time_step: 1e-5,
+        time_steps: 400000,
+        snapshot_interval: 100000,
+        potential: double_well_potential,
+    };
+
+    let mut propagator =
+        SplitOperatorPropagator::new(&config, gaussian_wavepacket(-0.5, 0.1, 0.0))
+            .expect("Failed to set up the propagator");
+    let snapshots = propagator
+        .propagate()
+        .expect("Failed to propagate the wavefunction");
+
+    // Plot the data
+    fs::create_dir_all("img").expect("Failed to create image directory");
+    let root_area = BitMapBackend::new("img/double_well_tunneling.png", (1280, 720))
+        .into_drawing_area();
+    root_area.fill(&WHITE).unwrap();
+
+    let mut ctx = ChartBuilder::on(&root_area)
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .caption(
+            "Tunneling in a double well potential using the split-operator method",
+            ("sans-serif", 40),
+        )
+        .build_cartesian_2d(-1.2..1.2, 0.0..4.5)
+        .unwrap();
+
+    ctx.configure_mesh()
+        .x_desc("x")
+        .y_desc("|ψ|²")
+        .axis_desc_style(("sans-serif", 20))
+        .draw()
+        .unwrap();
+
+    let colors = [BLUE, RED, GREEN, MAGENTA, CYAN];
+    for (i, snapshot) in snapshots.iter().enumerate() {
+        let color = colors[i % colors.len()];
+        let right_probability: f64 = snapshot
+            .probability_density
+            .iter()
+            .filter(|(x, _)| *x > 0.0)
+            .map(|(_, density)| density * config.step_size)
+            .sum();
+        ctx.draw_series(LineSeries::new(snapshot.probability_density.clone(), &color))
+            .unwrap()
+            .label(format!(
+                "t = {:.1}, P(x > 0) = {:.3}, E = {:.3}",
+                snapshot.time, right_probability, snapshot.energy
+            ))
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &color));
+    }
+
+    ctx.configure_series_labels()
+        .label_font(("sans-serif", 20))
+        .background_style(&WHITE.mix(0.8))
+        .border_style(&BLACK)
+        .draw()
+        .unwrap();
+}
@@ -0,0 +1,85 @@
+use std::fs;
+
+use sim_quantum::prelude::*;
+use plotters::prelude::*;
+
+fn main() {
+    // The two lowest states of the double well are split by tunneling through the
+    // barrier. Their superposition (ψ₀ + ψ₁)/√2 is localized in one well, and the
+    // interference between the two states shows up as fringes where W < 0.
+    let config = MatchingConfig {
+        x_min: -1.3,
+        x_max: 1.3,
+        x_match: 0.3,
+        step_size: 5e-4,
+        initial_energy: 0.0,
+        initial_energy_step_size: 1.0,
+        energy_step_size_cutoff: 1e-8,
+        max_iterations: 10000,
+        potential: double_well_potential,
+        using_numerov: true,
+        guarding_scale_factor: false,
+        quantum_number: None,
+        quadrature: Quadrature::Trapezoidal,
+    };
+    let mut solver = MatchingSolver::new(&config);
+    let states = solver
+        .solve_states(2)
+        .expect("Failed to solve the Schrodinger equation");
+
+    // A coarser grid is plenty for the plot
+    let points: Vec<(f64, f64)> = states[0]
+        .wavefunction
+        .iter()
+        .zip(states[1].wavefunction.iter())
+        .map(|((x, psi_0), (_, psi_1))| (*x, (psi_0 + psi_1) / 2.0_f64.sqrt()))
+        .step_by(10)
+        .collect();
+    let momenta: Vec<f64> = (0..=300).map(|i| -15.0 + 0.1 * i as f64).collect();
+    let distribution = wigner(&points, &momenta);
+    let w_max = distribution.iter().cloned().fold(0.0, |a: f64, b| a.max(b.abs()));
+
+    // Plot the data
+    fs::create_dir_all("img").expect("Failed to create image directory");
+    let root_area =
+        BitMapBackend::new("img/double_well_wigner.png", (1280, 720)).into_drawing_area();
+    root_area.fill(&WHITE).unwrap();
+
+    let mut ctx = ChartBuilder::on(&root_area)
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .caption(
+            format!(
+                "Wigner distribution of (ψ₀ + ψ₁)/√2 in the double well (E₀ = {:.3}, E₁ = {:.3})",
+                states[0].energy, states[1].energy
+            ),
+            ("sans-serif", 40),
+        )
+        .build_cartesian_2d(config.x_min..config.x_max, -15.0..15.0)
+        .unwrap();
+
+    ctx.configure_mesh()
+        .x_desc("x")
+        .y_desc("p")
+        .axis_desc_style(("sans-serif", 20))
+        .draw()
+        .unwrap();
+
+    // Red where W > 0 and blue where W < 0
+    let dx = points[1].0 - points[0].0;
+    let dp = momenta[1] - momenta[0];
+    ctx.draw_series(points.iter().enumerate().flat_map(|(j, (x, _))| {
+        let distribution = &distribution;
+        momenta.iter().enumerate().map(move |(m, p)| {
+            let w = distribution[[j, m]] / w_max;
+            let fade = (255.0 * (1.0 - w.abs())) as u8;
+            let color = if w >= 0.0 {
+                RGBColor(255, fade, fade)
+            } else {
+                RGBColor(fade, fade, 255)
+            };
+            Rectangle::new([(*x, *p), (x + dx, p + dp)], color.filled())
+        })
+    }))
+    .unwrap();
+}
@@ -0,0 +1,58 @@
+use std::fs;
+
+use sim_quantum::prelude::*;
+use plotters::prelude::*;
+
+fn main() {
+    // Solve the time-independent schrodinger equation by diagonalizing the Hamiltonian.
+    let config = DiagonalizationConfig {
+        x_min: -5.0,
+        x_max: 5.0,
+        step_size: 0.02,
+        potential: harmonic_potential,
+        num_states: 4,
+    };
+
+    let mut solver = DiagonalizationSolver::new(&config);
+    solver.solve().expect("Failed to solve the Schrodinger equation");
+
+    // Plot the data
+    fs::create_dir_all("img").expect("Failed to create image directory");
+    let root_area = BitMapBackend::new("img/harmonic_oscillator_diagonalization.png", (1280, 720))
+        .into_drawing_area();
+    root_area.fill(&WHITE).unwrap();
+
+    let mut ctx = ChartBuilder::on(&root_area)
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .caption(
+            "Harmonic oscillator wavefunctions using direct diagonalization",
+            ("sans-serif", 40),
+        )
+        .build_cartesian_2d(config.x_min..config.x_max, -1.0..1.0)
+        .unwrap();
+
+    ctx.configure_mesh()
+        .x_desc("x")
+        .y_desc("ψ")
+        .axis_desc_style(("sans-serif", 20))
+        .draw()
+        .unwrap();
+
+    let colors = [BLUE, RED, GREEN, MAGENTA];
+    for (n, energy) in solver.energies().iter().enumerate() {
+        let color = colors[n % colors.len()];
+        ctx.draw_series(LineSeries::new(solver.wavefunction_points_of(n), &color))
+            .unwrap()
+            .label(format!("E = {:.5}", energy))
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &color));
+    }
+
+    ctx.configure_series_labels()
+        .position(SeriesLabelPosition::LowerRight)
+        .label_font(("sans-serif", 20))
+        .background_style(&WHITE.mix(0.8))
+        .border_style(&BLACK)
+        .draw()
+        .unwrap();
+}
@@ -13,13 +13,16 @@ fn main() {
         initial_energy: 1.45,
         initial_energy_step_size: 0.1,
         energy_step_size_cutoff: 0.00001,
+        max_iterations: 10000,
         potential: harmonic_potential,
         using_numerov: true,
-        guarding_scale_factor: true
+        guarding_scale_factor: true,
+        quantum_number: None,
+        quadrature: Quadrature::Trapezoidal,
     };
 
     let mut solver = MatchingSolver::new(&config);
-    solver.solve();
+    solver.solve().expect("Failed to solve the Schrodinger equation");
 
     // Plot the data
     fs::create_dir_all("img").expect("Failed to create image directory");
@@ -113,7 +116,7 @@ fn main() {
 
     config.using_numerov = false;
     let mut solver = MatchingSolver::new(&config);
-    solver.solve();
+    solver.solve().expect("Failed to solve the Schrodinger equation");
 
     lower_chart
         .draw_series(LineSeries::new(
@@ -140,5 +143,10 @@ fn main() {
 }
 
 fn first_excited_state(x: f64) -> f64 {
-    -(1.0 / std::f64::consts::PI).powf(0.25) * 2.0_f64.sqrt() * x * (-0.5 * x * x).exp()
+    // The solver's wavefunction starts out positive at x_min, so its sign is
+    // opposite to the exact one
+    let exact = HarmonicOscillator { omega: 1.0 }
+        .wavefunction_points(1, &[x])
+        .expect("The harmonic oscillator has every level");
+    -exact[0].1
 }
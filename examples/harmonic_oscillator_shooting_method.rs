@@ -7,18 +7,23 @@ fn main() {
     // Solve the time-independent schrodinger equation using the shooting method for
     // odd parity wavefunctions.
     let config = ShootingConfig {
+        x_min: None,
         x_max: 7.0,
         step_size: 0.01,
         initial_energy: 0.0,
         intitial_energy_step_size: 0.01,
         energy_step_size_cutoff: 0.000001,
+        max_iterations: 10000,
         wavefunction_cutoff: 100.0,
         potential: harmonic_potential,
         parity: Parity::Odd,
+        using_numerov: true,
+        quantum_number: None,
+        quadrature: Quadrature::Trapezoidal,
     };
 
     let mut solver = ShootingSolver::new(&config);
-    solver.solve();
+    solver.solve().expect("Failed to solve the Schrodinger equation");
 
     // Plot the data
     fs::create_dir_all("img").expect("Failed to create image directory");
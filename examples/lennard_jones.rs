@@ -14,14 +14,20 @@ fn main() {
         initial_energy: -5.0,
         initial_energy_step_size: 0.1,
         energy_step_size_cutoff: 0.001,
-        potential: lennard_jones_potential,
+        max_iterations: 10000,
+        potential: LennardJones {
+            epsilon: 10.0,
+            sigma: 1.0,
+        },
         using_numerov: true,
-        guarding_scale_factor: false
+        guarding_scale_factor: false,
+        quantum_number: None,
+        quadrature: Quadrature::Trapezoidal,
     };
 
 
     let mut solver = MatchingSolver::new(&config);
-    solver.solve();
+    solver.solve().expect("Failed to solve the Schrodinger equation");
 
     // Plot the data
     fs::create_dir_all("img").expect("Failed to create image directory");
@@ -0,0 +1,98 @@
+use std::fs;
+
+use sim_quantum::prelude::*;
+use plotters::prelude::*;
+
+fn main() {
+    // Find the ground state with diffusion Monte Carlo and check it against the
+    // matching method.
+    let dmc_config = DiffusionMonteCarloConfig {
+        x_min: 0.8,
+        x_max: 4.0,
+        step_size: 0.01,
+        potential: lennard_jones_potential,
+        initial_position: 1.12,
+        target_walkers: 1000,
+        time_step: 1e-4,
+        thermalization_steps: 20000,
+        time_steps: 100000,
+        block_size: 5000,
+        seed: 1,
+        quadrature: Quadrature::Trapezoidal,
+    };
+    let mut dmc_solver = DiffusionMonteCarloSolver::new(&dmc_config);
+    dmc_solver.solve().expect("Failed to solve the Schrodinger equation");
+
+    let matching_config = MatchingConfig {
+        x_min: 0.5,
+        x_max: 5.0,
+        x_match: 1.4,
+        step_size: 0.001,
+        initial_energy: -5.0,
+        initial_energy_step_size: 0.1,
+        energy_step_size_cutoff: 1e-8,
+        max_iterations: 10000,
+        potential: lennard_jones_potential,
+        using_numerov: true,
+        guarding_scale_factor: false,
+        quantum_number: Some(0),
+        quadrature: Quadrature::Trapezoidal,
+    };
+    let mut matching_solver = MatchingSolver::new(&matching_config);
+    matching_solver.solve().expect("Failed to solve the Schrodinger equation");
+
+    // Plot the data
+    fs::create_dir_all("img").expect("Failed to create image directory");
+    let root_area = BitMapBackend::new("img/lennard_jones_diffusion_monte_carlo.png", (1280, 720))
+        .into_drawing_area();
+    root_area.fill(&WHITE).unwrap();
+
+    let mut ctx = ChartBuilder::on(&root_area)
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .caption(
+            "Lennard-Jones ground state using diffusion Monte Carlo",
+            ("sans-serif", 40),
+        )
+        .build_cartesian_2d(dmc_config.x_min..dmc_config.x_max, 0.0..3.0)
+        .unwrap();
+
+    ctx.configure_mesh()
+        .x_desc("x")
+        .y_desc("ψ")
+        .axis_desc_style(("sans-serif", 20))
+        .draw()
+        .unwrap();
+
+    ctx.draw_series(
+        dmc_solver
+            .wavefunction_points()
+            .into_iter()
+            .map(|point| Circle::new(point, 3, BLUE.filled())),
+    )
+    .unwrap()
+    .label(format!(
+        "Diffusion Monte Carlo, E = {:.4} ± {:.4}",
+        dmc_solver.energy(),
+        dmc_solver.error()
+    ))
+    .legend(|(x, y)| Circle::new((x + 10, y), 3, BLUE.filled()));
+
+    ctx.draw_series(LineSeries::new(
+        matching_solver
+            .wavefunction_points()
+            .into_iter()
+            .map(|(x, psi)| (x, psi.abs())),
+        &RED,
+    ))
+    .unwrap()
+    .label(format!("Matching method, E = {:.4}", matching_solver.energy()))
+    .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &RED));
+
+    ctx.configure_series_labels()
+        .label_font(("sans-serif", 20))
+        .background_style(&WHITE.mix(0.8))
+        .border_style(&BLACK)
+        .draw()
+        .unwrap();
+}
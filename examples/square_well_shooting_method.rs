@@ -7,6 +7,7 @@ use sim_quantum::prelude::*;
 fn main() {
     // Solve the time-independent schrodinger equation using the shooting method.
     let even_config = ShootingConfig {
+        x_min: None,
         x_max: 0.55,
         step_size: L * 1.15 / 10000.0,
         initial_energy: 0.0,
@@ -14,17 +15,21 @@ fn main() {
         wavefunction_cutoff: 100.0,
         potential: box_potential,
         energy_step_size_cutoff: 0.000001,
+        max_iterations: 10000,
         parity: Parity::Even,
+        using_numerov: true,
+        quantum_number: None,
+        quadrature: Quadrature::Trapezoidal,
     };
 
     let mut even_solver = ShootingSolver::new(&even_config);
-    even_solver.solve();
+    even_solver.solve().expect("Failed to solve the Schrodinger equation");
 
     let mut odd_config = even_config.clone();
     odd_config.parity = Parity::Odd;
 
     let mut odd_solver = ShootingSolver::new(&odd_config);
-    odd_solver.solve();
+    odd_solver.solve().expect("Failed to solve the Schrodinger equation");
 
     // Plot the data
     fs::create_dir_all("img").expect("Failed to create image directory");
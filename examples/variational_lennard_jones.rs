@@ -9,11 +9,21 @@ fn main() {
         x_min: 0.5,
         x_max: 5.0,
         step_size: 0.01,
-        potential: lennard_jones_potential
+        potential: lennard_jones_potential,
+        initial_wavefunction: |x: f64| (-8.0 * (x - 1.2).powi(2)).exp(),
+        seed: 0,
+        max_iterations: 100000,
+        schedule: StepSchedule::Exponential {
+            initial: 0.05,
+            decay: 0.5,
+            interval: 25000,
+        },
+        tolerance: None,
+        quadrature: Quadrature::Trapezoidal,
     };
 
     let mut solver = VariationalSolver::new(&config);
-    solver.solve();
+    solver.solve().expect("Failed to solve the Schrodinger equation");
 
     // Plot the data
     fs::create_dir_all("img").expect("Failed to create image directory");
@@ -0,0 +1,66 @@
+//! Errors that can occur while solving the Schrodinger equation.
+
+use std::fmt;
+
+use ndarray_linalg::error::LinalgError;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The solver did not converge within the maximum number of iterations.
+    NonConvergence { iterations: usize },
+    /// The configuration is inconsistent, e.g. `x_match` outside of `[x_min, x_max]`.
+    InvalidConfig(String),
+    /// The wavefunction or energy overflowed to infinity or NaN.
+    NumericalOverflow,
+    /// The step size leaves too few grid points for the method to work.
+    StepSizeTooLarge { step_size: f64 },
+    /// The factor used to join the left and right wavefunctions of the matching
+    /// method is outside of the range allowed by `guarding_scale_factor`.
+    ScaleFactorOutOfRange { scale_factor: f64 },
+    /// No energy interval containing the level with this many nodes could be found.
+    LevelNotFound { nodes: usize },
+    /// The Hamiltonian matrix could not be diagonalized.
+    Diagonalization(LinalgError),
+    /// An observer stopped the solver after this many iterations.
+    Cancelled { iterations: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NonConvergence { iterations } => {
+                write!(f, "solver did not converge after {iterations} iterations")
+            }
+            Error::InvalidConfig(reason) => write!(f, "invalid configuration: {reason}"),
+            Error::NumericalOverflow => write!(f, "numerical overflow"),
+            Error::StepSizeTooLarge { step_size } => {
+                write!(f, "step size {step_size} leaves too few grid points")
+            }
+            Error::ScaleFactorOutOfRange { scale_factor } => {
+                write!(f, "matching scale factor {scale_factor} is out of range")
+            }
+            Error::LevelNotFound { nodes } => {
+                write!(f, "could not bracket the level with {nodes} nodes")
+            }
+            Error::Diagonalization(err) => write!(f, "diagonalization failed: {err}"),
+            Error::Cancelled { iterations } => {
+                write!(f, "solver was cancelled after {iterations} iterations")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Diagonalization(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<LinalgError> for Error {
+    fn from(err: LinalgError) -> Self {
+        Error::Diagonalization(err)
+    }
+}
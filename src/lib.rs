@@ -1,3 +1,4 @@
+pub mod error;
 pub mod prelude;
 pub mod physics;
 pub mod utils;
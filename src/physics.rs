@@ -1,31 +1,45 @@
 pub mod solvers;
+pub mod potentials;
+pub mod dynamics;
+pub mod scattering;
+pub mod observables;
+pub mod matrix_elements;
+pub mod momentum_space;
+pub mod phase_space;
+pub mod analytic;
+
+use crate::physics::potentials::{
+    DoubleWell, HarmonicOscillator, LennardJones, Potential, SquareWell, WALL_HEIGHT,
+};
 
 pub const L: f64 = 0.5;
 
 pub fn box_potential(x: f64) -> f64 {
-    if x.abs() < L {
-        0.0
-    } else {
-        100000.0
+    SquareWell {
+        width: 2.0 * L,
+        depth: WALL_HEIGHT,
     }
+    .shifted(WALL_HEIGHT)
+    .value(x)
 }
 
 pub fn double_well_potential(x: f64) -> f64 {
-    if x.abs() <= 0.1 {
-        100.0
-    } else if x.abs() < 1.0 {
-        0.0
-    } else {
-        1e5
+    DoubleWell {
+        barrier_height: 100.0,
+        barrier_width: 0.2,
+        outer_width: 2.0,
     }
+    .value(x)
 }
 
 pub fn harmonic_potential(x: f64) -> f64 {
-    0.5 * x * x
+    HarmonicOscillator { omega: 1.0 }.value(x)
 }
 
 pub fn lennard_jones_potential(x: f64) -> f64 {
-    let epsilon = 10.0;
-    let sigma = 1.0;
-    4.0 * epsilon * ((sigma / x).powf(12.0) - (sigma / x).powf(6.0))
+    LennardJones {
+        epsilon: 10.0,
+        sigma: 1.0,
+    }
+    .value(x)
 }
@@ -0,0 +1,454 @@
+//! Exact energies and wavefunctions of potentials with known solutions, for
+//! validating the solvers. All wavefunctions are normalized so that ∫|ψ|²dx = 1
+//! and use units where ħ = m = 1. Levels are indexed by their number of nodes `n`,
+//! starting from the ground state.
+
+use std::f64::consts::PI;
+
+use crate::error::Error;
+use crate::physics::potentials::{HarmonicOscillator, Potential, SquareWell, WALL_HEIGHT};
+use crate::utils::root_finding::find_root;
+use crate::utils::special::{airy, gegenbauer, hermite, laguerre, ln_gamma};
+
+pub trait ExactSolution: Potential {
+    /// Returns the energy of the level with `n` nodes, or `Error::LevelNotFound` if
+    /// the potential has no such bound state.
+    fn energy(&self, n: usize) -> Result<f64, Error>;
+
+    /// Returns (x, ψ) points of the level with `n` nodes at each of `x`.
+    fn wavefunction_points(&self, n: usize, x: &[f64]) -> Result<Vec<(f64, f64)>, Error>;
+}
+
+/// An infinite square well of the given width centered at x = 0. As a potential
+/// the walls have height `WALL_HEIGHT`, like `box_potential`.
+#[derive(Clone, Copy, Debug)]
+pub struct InfiniteSquareWell {
+    pub width: f64,
+}
+
+impl Potential for InfiniteSquareWell {
+    fn value(&self, x: f64) -> f64 {
+        if x.abs() < 0.5 * self.width {
+            0.0
+        } else {
+            WALL_HEIGHT
+        }
+    }
+}
+
+impl ExactSolution for InfiniteSquareWell {
+    /// E = (n + 1)²π²/2L²
+    fn energy(&self, n: usize) -> Result<f64, Error> {
+        let k = (n + 1) as f64 * PI / self.width;
+        Ok(0.5 * k * k)
+    }
+
+    /// ψ = √(2/L) sin((n + 1)π(x + L/2)/L) inside the well
+    fn wavefunction_points(&self, n: usize, x: &[f64]) -> Result<Vec<(f64, f64)>, Error> {
+        let half_width = 0.5 * self.width;
+        let k = (n + 1) as f64 * PI / self.width;
+        let amplitude = (2.0 / self.width).sqrt();
+
+        Ok(x.iter()
+            .map(|x| {
+                if x.abs() < half_width {
+                    (*x, amplitude * (k * (x + half_width)).sin())
+                } else {
+                    (*x, 0.0)
+                }
+            })
+            .collect())
+    }
+}
+
+impl ExactSolution for HarmonicOscillator {
+    /// E = (n + ½)ω
+    fn energy(&self, n: usize) -> Result<f64, Error> {
+        Ok((n as f64 + 0.5) * self.omega)
+    }
+
+    /// ψ = (ω/π)^¼ (2ⁿn!)^(-½) Hₙ(√ω x) exp(-ωx²/2)
+    fn wavefunction_points(&self, n: usize, x: &[f64]) -> Result<Vec<(f64, f64)>, Error> {
+        let ln_normalization = 0.25 * (self.omega / PI).ln()
+            - 0.5 * (n as f64 * 2.0_f64.ln() + ln_gamma(n as f64 + 1.0));
+
+        Ok(x.iter()
+            .map(|x| {
+                let xi = self.omega.sqrt() * x;
+                (*x, hermite(n, xi) * (ln_normalization - 0.5 * xi * xi).exp())
+            })
+            .collect())
+    }
+}
+
+impl SquareWell {
+    /// Returns z = ka for the level with `n` nodes, where a is half the width and
+    /// k is the wavenumber inside the well. Even levels solve z tan z = √(z₀² - z²)
+    /// and odd levels solve -z cot z = √(z₀² - z²), with z₀ = a√(2 depth). The
+    /// n-th root lies between nπ/2 and (n + 1)π/2.
+    fn scaled_wavenumber(&self, n: usize) -> Result<f64, Error> {
+        let half_width = 0.5 * self.width;
+        let z0 = half_width * (2.0 * self.depth).sqrt();
+        let lower = n as f64 * 0.5 * PI;
+        if self.depth <= 0.0 || lower >= z0 {
+            return Err(Error::LevelNotFound { nodes: n });
+        }
+        let upper = ((n + 1) as f64 * 0.5 * PI).min(z0);
+
+        // Multiplied through by cos z or sin z so that there are no poles
+        let outside = |z: f64| (z0 * z0 - z * z).max(0.0).sqrt();
+        let even = n.is_multiple_of(2);
+        let f = |z: f64| {
+            if even {
+                z * z.sin() - outside(z) * z.cos()
+            } else {
+                z * z.cos() + outside(z) * z.sin()
+            }
+        };
+
+        let margin = 1e-9 * (upper - lower);
+        find_root(f, (lower + margin)..=(upper - margin), 1e-14, 100)
+    }
+}
+
+impl ExactSolution for SquareWell {
+    /// E = z²/2a² - depth, with z found by `scaled_wavenumber`
+    fn energy(&self, n: usize) -> Result<f64, Error> {
+        let half_width = 0.5 * self.width;
+        let z = self.scaled_wavenumber(n)?;
+        Ok(0.5 * z * z / (half_width * half_width) - self.depth)
+    }
+
+    /// ψ = A cos kx (even) or A sin kx (odd) inside, decaying as exp(-κ|x|) outside
+    fn wavefunction_points(&self, n: usize, x: &[f64]) -> Result<Vec<(f64, f64)>, Error> {
+        let half_width = 0.5 * self.width;
+        let energy = self.energy(n)?;
+        let k = self.scaled_wavenumber(n)? / half_width;
+        let kappa = (-2.0 * energy).sqrt();
+
+        let even = n.is_multiple_of(2);
+        let inside = |x: f64| if even { (k * x).cos() } else { (k * x).sin() };
+        let edge = inside(half_width);
+        let parity = if even { 1.0 } else { -1.0 };
+        let norm = 2.0 * (half_width * 0.5 + parity * (2.0 * k * half_width).sin() / (4.0 * k))
+            + edge * edge / kappa;
+        let amplitude = norm.sqrt().recip();
+
+        Ok(x.iter()
+            .map(|x| {
+                let psi = if x.abs() < half_width {
+                    inside(*x)
+                } else {
+                    let sign = if *x < 0.0 { parity } else { 1.0 };
+                    sign * edge * (-kappa * (x.abs() - half_width)).exp()
+                };
+                (*x, amplitude * psi)
+            })
+            .collect())
+    }
+}
+
+/// The Morse potential D(1 - exp(-α(x - x₀)))², with its minimum of zero at x₀.
+#[derive(Clone, Copy, Debug)]
+pub struct Morse {
+    pub depth: f64,
+    pub alpha: f64,
+    pub center: f64,
+}
+
+impl Morse {
+    /// Returns λ = √(2D)/α. The levels with n < λ - ½ are bound.
+    fn lambda(&self) -> f64 {
+        (2.0 * self.depth).sqrt() / self.alpha
+    }
+}
+
+impl Potential for Morse {
+    fn value(&self, x: f64) -> f64 {
+        let y = 1.0 - (-self.alpha * (x - self.center)).exp();
+        self.depth * y * y
+    }
+}
+
+impl ExactSolution for Morse {
+    /// E = α²(λ² - (λ - n - ½)²)/2
+    fn energy(&self, n: usize) -> Result<f64, Error> {
+        let lambda = self.lambda();
+        let s = lambda - n as f64 - 0.5;
+        if s <= 0.0 {
+            return Err(Error::LevelNotFound { nodes: n });
+        }
+        Ok(0.5 * self.alpha * self.alpha * (lambda * lambda - s * s))
+    }
+
+    /// ψ = N z^s exp(-z/2) Lₙ^(2s)(z) with z = 2λ exp(-α(x - x₀)) and s = λ - n - ½
+    fn wavefunction_points(&self, n: usize, x: &[f64]) -> Result<Vec<(f64, f64)>, Error> {
+        self.energy(n)?;
+        let lambda = self.lambda();
+        let s = lambda - n as f64 - 0.5;
+        let ln_normalization = 0.5
+            * ((2.0 * s * self.alpha).ln() + ln_gamma(n as f64 + 1.0)
+                - ln_gamma(n as f64 + 2.0 * s + 1.0));
+
+        Ok(x.iter()
+            .map(|x| {
+                let z = 2.0 * lambda * (-self.alpha * (x - self.center)).exp();
+                let psi = (ln_normalization + s * z.ln() - 0.5 * z).exp()
+                    * laguerre(n, 2.0 * s, z);
+                (*x, psi)
+            })
+            .collect())
+    }
+}
+
+/// The Pöschl-Teller potential -α²λ(λ + 1)/2 sech²(αx).
+#[derive(Clone, Copy, Debug)]
+pub struct PoschlTeller {
+    pub lambda: f64,
+    pub alpha: f64,
+}
+
+impl Potential for PoschlTeller {
+    fn value(&self, x: f64) -> f64 {
+        let sech = (self.alpha * x).cosh().recip();
+        -0.5 * self.alpha * self.alpha * self.lambda * (self.lambda + 1.0) * sech * sech
+    }
+}
+
+impl ExactSolution for PoschlTeller {
+    /// E = -α²(λ - n)²/2 for n < λ
+    fn energy(&self, n: usize) -> Result<f64, Error> {
+        let s = self.lambda - n as f64;
+        if s <= 0.0 {
+            return Err(Error::LevelNotFound { nodes: n });
+        }
+        Ok(-0.5 * self.alpha * self.alpha * s * s)
+    }
+
+    /// ψ = N sechˢ(αx) Cₙ^(s + ½)(tanh αx) with s = λ - n
+    fn wavefunction_points(&self, n: usize, x: &[f64]) -> Result<Vec<(f64, f64)>, Error> {
+        self.energy(n)?;
+        let s = self.lambda - n as f64;
+        let ln_norm = PI.ln() + (1.0 - 2.0 * s) * 2.0_f64.ln() + ln_gamma(n as f64 + 2.0 * s + 1.0)
+            - ln_gamma(n as f64 + 1.0)
+            - (2.0 * s * self.alpha).ln()
+            - 2.0 * ln_gamma(s + 0.5);
+        let amplitude = (-0.5 * ln_norm).exp();
+
+        Ok(x.iter()
+            .map(|x| {
+                let t = (self.alpha * x).tanh();
+                let sech = (self.alpha * x).cosh().recip();
+                (*x, amplitude * sech.powf(s) * gegenbauer(n, s + 0.5, t))
+            })
+            .collect())
+    }
+}
+
+/// The linear potential Fx for x > 0 with a wall of height `WALL_HEIGHT` at x = 0,
+/// a model of a particle bouncing under gravity or of a triangular well.
+#[derive(Clone, Copy, Debug)]
+pub struct TriangularWell {
+    pub slope: f64,
+}
+
+impl TriangularWell {
+    /// Returns the zero of Ai with `n` zeros above it, starting from its
+    /// asymptotic approximation -(3π(4n + 3)/8)^(2/3).
+    fn airy_zero(n: usize) -> Result<f64, Error> {
+        let guess = -(3.0 * PI * (4 * n + 3) as f64 / 8.0).powf(2.0 / 3.0);
+        find_root(|z| airy(z).ai, (guess - 0.05)..=(guess + 0.05), 1e-14, 100)
+    }
+
+    /// Returns (2F)^(1/3), the inverse of the length scale of the well.
+    fn scale(&self) -> f64 {
+        (2.0 * self.slope).cbrt()
+    }
+}
+
+impl Potential for TriangularWell {
+    fn value(&self, x: f64) -> f64 {
+        if x > 0.0 {
+            self.slope * x
+        } else {
+            WALL_HEIGHT
+        }
+    }
+}
+
+impl ExactSolution for TriangularWell {
+    /// E = -aₙ(F²/2)^(1/3), where aₙ is a zero of Ai
+    fn energy(&self, n: usize) -> Result<f64, Error> {
+        Ok(-Self::airy_zero(n)? * (0.5 * self.slope * self.slope).cbrt())
+    }
+
+    /// ψ = Ai((2F)^(1/3)x + aₙ)(2F)^(1/6)/|Ai'(aₙ)| for x > 0
+    fn wavefunction_points(&self, n: usize, x: &[f64]) -> Result<Vec<(f64, f64)>, Error> {
+        let zero = Self::airy_zero(n)?;
+        let scale = self.scale();
+        let amplitude = scale.sqrt() / airy(zero).ai_prime.abs();
+
+        Ok(x.iter()
+            .map(|x| {
+                if *x > 0.0 {
+                    (*x, amplitude * airy(scale * x + zero).ai)
+                } else {
+                    (*x, 0.0)
+                }
+            })
+            .collect())
+    }
+}
+
+/// The radial equation of a hydrogen-like atom with nuclear charge Z and angular
+/// momentum `l`. As a potential it is the bare -Z/r, for use with `RadialSolver`,
+/// which adds the centrifugal term itself. The wavefunction is u(r) = rR(r).
+#[derive(Clone, Copy, Debug)]
+pub struct HydrogenRadial {
+    pub charge: f64,
+    pub l: usize,
+}
+
+impl Potential for HydrogenRadial {
+    fn value(&self, r: f64) -> f64 {
+        -self.charge / r
+    }
+}
+
+impl ExactSolution for HydrogenRadial {
+    /// E = -Z²/2N² with principal quantum number N = n + l + 1
+    fn energy(&self, n: usize) -> Result<f64, Error> {
+        let principal = (n + self.l + 1) as f64;
+        Ok(-0.5 * self.charge * self.charge / (principal * principal))
+    }
+
+    /// u = N ρ^(l + 1) exp(-ρ/2) Lₙ^(2l + 1)(ρ) with ρ = 2Zr/N
+    fn wavefunction_points(&self, n: usize, r: &[f64]) -> Result<Vec<(f64, f64)>, Error> {
+        let principal = (n + self.l + 1) as f64;
+        let l = self.l as f64;
+        let scale = 2.0 * self.charge / principal;
+        let ln_normalization = 0.5
+            * (scale.ln() + ln_gamma(n as f64 + 1.0)
+                - (2.0 * principal).ln()
+                - ln_gamma(n as f64 + 2.0 * l + 2.0));
+
+        Ok(r.iter()
+            .map(|r| {
+                let rho = scale * r;
+                let u = if rho <= 0.0 {
+                    0.0
+                } else {
+                    (ln_normalization + (l + 1.0) * rho.ln() - 0.5 * rho).exp()
+                        * laguerre(n, 2.0 * l + 1.0, rho)
+                };
+                (*r, u)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::physics::analytic::*;
+    use crate::physics::solvers::radial::{RadialConfig, RadialSolver};
+    use crate::physics::solvers::shooting::{Parity, ShootingConfig, ShootingSolver};
+    use crate::physics::solvers::Solver;
+    use crate::utils::integration::{trapezoidal, Quadrature};
+
+    fn norm(points: &[(f64, f64)], step_size: f64) -> f64 {
+        let density: Vec<f64> = points.iter().map(|(_, psi)| psi * psi).collect();
+        trapezoidal(&density, &step_size)
+    }
+
+    #[test]
+    fn normalized_eigenstates() {
+        let step_size = 1e-3;
+        let x: Vec<f64> = (0..=40000).map(|i| -20.0 + step_size * i as f64).collect();
+        let systems: Vec<(Box<dyn ExactSolution>, usize)> = vec![
+            (Box::new(InfiniteSquareWell { width: 2.0 }), 4),
+            (Box::new(HarmonicOscillator { omega: 1.5 }), 6),
+            (Box::new(SquareWell { width: 2.0, depth: 10.0 }), 3),
+            (Box::new(Morse { depth: 8.0, alpha: 1.0, center: -2.0 }), 3),
+            (Box::new(PoschlTeller { lambda: 3.5, alpha: 1.2 }), 3),
+            (Box::new(TriangularWell { slope: 1.0 }), 3),
+        ];
+
+        for (system, levels) in systems.iter() {
+            for n in 0..*levels {
+                let points = system.wavefunction_points(n, &x).unwrap();
+                assert!((norm(&points, step_size) - 1.0).abs() < 1e-6);
+
+                // Hψ = Eψ away from any discontinuity of the potential
+                let energy = system.energy(n).unwrap();
+                for i in [15500, 19300, 21700] {
+                    let (x, psi) = points[i];
+                    let second_derivative =
+                        (points[i + 1].1 - 2.0 * psi + points[i - 1].1) / (step_size * step_size);
+                    let residual = -0.5 * second_derivative + (system.value(x) - energy) * psi;
+                    assert!(residual.abs() < 1e-4);
+                }
+            }
+        }
+
+        // Only finitely many levels are bound
+        assert!(matches!(
+            (SquareWell { width: 2.0, depth: 10.0 }).energy(3),
+            Err(Error::LevelNotFound { nodes: 3 })
+        ));
+        assert!((PoschlTeller { lambda: 3.5, alpha: 1.2 }).energy(4).is_err());
+    }
+
+    #[test]
+    fn solvers_match_exact_levels() {
+        let well = SquareWell {
+            width: 2.0,
+            depth: 10.0,
+        };
+        let mut solver = ShootingSolver::new(&ShootingConfig {
+            x_min: None,
+            x_max: 6.0,
+            step_size: 1e-3,
+            initial_energy: -10.0,
+            intitial_energy_step_size: 0.1,
+            wavefunction_cutoff: 100.0,
+            potential: well,
+            energy_step_size_cutoff: 1e-10,
+            max_iterations: 10000,
+            parity: Parity::Odd,
+            using_numerov: false,
+            quantum_number: Some(1),
+            quadrature: Quadrature::Trapezoidal,
+        });
+        solver.solve().unwrap();
+        // The edges of the well fall between grid points, which limits the solver to
+        // first order in the step size
+        assert!((solver.energy() - well.energy(1).unwrap()).abs() < 5e-3);
+
+        let hydrogen = HydrogenRadial { charge: 2.0, l: 1 };
+        let mut solver = RadialSolver::new(&RadialConfig {
+            r_max: 40.0,
+            r_match: 1.0,
+            step_size: 1e-3,
+            l: 1,
+            initial_energy: -1.0,
+            initial_energy_step_size: 0.1,
+            energy_step_size_cutoff: 1e-10,
+            max_iterations: 10000,
+            potential: hydrogen,
+            guarding_scale_factor: false,
+            quantum_number: None,
+            quadrature: Quadrature::Trapezoidal,
+        });
+        let states = solver.solve_states(2).unwrap();
+        for (n, state) in states.iter().enumerate() {
+            assert!((state.energy - hydrogen.energy(n).unwrap()).abs() < 1e-5);
+
+            let r: Vec<f64> = state.wavefunction.iter().map(|(r, _)| *r).collect();
+            let exact = hydrogen.wavefunction_points(n, &r).unwrap();
+            let sign = state.wavefunction[1000].1.signum() * exact[1000].1.signum();
+            for ((_, u), (_, u_exact)) in state.wavefunction.iter().zip(exact.iter()) {
+                assert!((u - sign * u_exact).abs() < 1e-4);
+            }
+        }
+    }
+}
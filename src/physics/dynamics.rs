@@ -0,0 +1,31 @@
+//! Propagation of wavefunctions under the time-dependent Schrodinger equation
+//! in one dimension.
+
+pub mod split_operator;
+pub mod crank_nicolson;
+
+use std::f64::consts::PI;
+
+use num_complex::Complex64;
+
+/// The state of a propagated wavefunction at one instant.
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+    pub time: f64,
+    /// The (x, |ψ(x, t)|²) points of the probability density.
+    pub probability_density: Vec<(f64, f64)>,
+    /// ∫|ψ|²dx, which should stay at 1 for a unitary propagation.
+    pub norm: f64,
+    /// The expectation value of the Hamiltonian.
+    pub energy: f64,
+}
+
+/// Returns a normalized Gaussian wavepacket centered at `x0` with mean momentum
+/// `p0`. The probability density |ψ|² has standard deviation `sigma`.
+pub fn gaussian_wavepacket(x0: f64, sigma: f64, p0: f64) -> impl Fn(f64) -> Complex64 {
+    let amplitude = (2.0 * PI * sigma * sigma).powf(-0.25);
+    move |x| {
+        let envelope = amplitude * (-(x - x0) * (x - x0) / (4.0 * sigma * sigma)).exp();
+        Complex64::from_polar(envelope, p0 * x)
+    }
+}
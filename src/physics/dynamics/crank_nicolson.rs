@@ -0,0 +1,300 @@
+//! Crank-Nicolson method for propagating a wavefunction in time. Each time step
+//! solves (1 + iHΔt/2)ψ(t + Δt) = (1 - iHΔt/2)ψ(t) with the tridiagonal finite
+//! difference Hamiltonian, which is unitary and conserves the discrete energy.
+//! The wavefunction vanishes at `x_min` and `x_max`. To let a wavepacket leave the
+//! grid instead of reflecting off these walls, a complex absorbing potential can
+//! be placed along both edges.
+
+use num_complex::Complex64;
+
+use crate::error::Error;
+use crate::physics::dynamics::Snapshot;
+use crate::physics::potentials::Potential;
+use crate::physics::solvers::observer::{Control, Observer, Progress, Silent};
+use crate::utils::integration::trapezoidal;
+use crate::utils::tridiagonal::solve_tridiagonal;
+
+/// A complex absorbing potential -iW(x) added inside a layer of the given width
+/// at each edge of the grid. W rises quadratically from zero at the inner side
+/// of the layer to `strength` at the edge.
+#[derive(Clone, Copy, Debug)]
+pub struct AbsorbingBoundary {
+    pub width: f64,
+    pub strength: f64,
+}
+
+impl AbsorbingBoundary {
+    /// Returns W at a distance `depth` from the nearest edge of the grid.
+    fn value(&self, depth: f64) -> f64 {
+        if depth < self.width {
+            let penetration = (self.width - depth) / self.width;
+            self.strength * penetration * penetration
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Configuration for the Crank-Nicolson propagator
+#[derive(Clone)]
+pub struct CrankNicolsonConfig<P> {
+    pub x_min: f64,
+    pub x_max: f64,
+    pub step_size: f64,
+    pub time_step: f64,
+    /// The number of time steps taken by `propagate`.
+    pub time_steps: usize,
+    /// The number of time steps between snapshots taken by `propagate`.
+    pub snapshot_interval: usize,
+    pub potential: P,
+    pub absorbing_boundary: Option<AbsorbingBoundary>,
+}
+
+/// A propagator for the time-dependent Schrodinger equation using the
+/// Crank-Nicolson method.
+pub struct CrankNicolsonPropagator<P> {
+    pub config: CrankNicolsonConfig<P>,
+    time: f64,
+    x_vals: Vec<f64>,
+    potential_vals: Vec<f64>,
+    absorption_vals: Vec<f64>,
+    // Diagonals of 1 + iHΔt/2 on the interior points. The off diagonals are constant.
+    lower: Vec<Complex64>,
+    diagonal: Vec<Complex64>,
+    upper: Vec<Complex64>,
+    wavefunction: Vec<Complex64>,
+}
+
+impl<P: Potential + Clone> CrankNicolsonPropagator<P> {
+    /// Samples the initial wavefunction on the grid, setting it to zero at the
+    /// endpoints, and builds the left hand side matrix.
+    pub fn new(
+        config: &CrankNicolsonConfig<P>,
+        initial_wavefunction: impl Fn(f64) -> Complex64,
+    ) -> Result<Self, Error> {
+        if config.step_size <= 0.0 || config.time_step <= 0.0 {
+            return Err(Error::InvalidConfig(
+                "step_size and time_step must be positive".to_string(),
+            ));
+        }
+        if config.x_min >= config.x_max {
+            return Err(Error::InvalidConfig(
+                "x_min must be less than x_max".to_string(),
+            ));
+        }
+        if config.snapshot_interval == 0 {
+            return Err(Error::InvalidConfig(
+                "snapshot_interval must be at least 1".to_string(),
+            ));
+        }
+        if let Some(boundary) = config.absorbing_boundary {
+            if boundary.width <= 0.0 || boundary.strength < 0.0 {
+                return Err(Error::InvalidConfig(
+                    "absorbing_boundary must have a positive width and non-negative strength"
+                        .to_string(),
+                ));
+            }
+        }
+
+        let steps = ((config.x_max - config.x_min) / config.step_size).round() as usize + 1;
+        if steps < 3 {
+            return Err(Error::StepSizeTooLarge {
+                step_size: config.step_size,
+            });
+        }
+
+        let x_vals: Vec<f64> = (0..steps)
+            .map(|i| config.x_min + (i as f64) * config.step_size)
+            .collect();
+        let potential_vals: Vec<f64> = x_vals.iter().map(|x| config.potential.value(*x)).collect();
+
+        let absorption_vals: Vec<f64> = match config.absorbing_boundary {
+            Some(boundary) => x_vals
+                .iter()
+                .map(|x| boundary.value((x - config.x_min).min(config.x_max - x)))
+                .collect(),
+            None => vec![0.0; steps],
+        };
+
+        let kinetic = 0.5 / (config.step_size * config.step_size);
+        let half_step = 0.5 * config.time_step;
+        let interior = steps - 2;
+
+        let off_diagonal = Complex64::new(0.0, -kinetic * half_step);
+        let lower = vec![off_diagonal; interior];
+        let upper = vec![off_diagonal; interior];
+        let diagonal = (1..steps - 1)
+            .map(|i| {
+                Complex64::new(
+                    1.0 + absorption_vals[i] * half_step,
+                    (2.0 * kinetic + potential_vals[i]) * half_step,
+                )
+            })
+            .collect();
+
+        let mut wavefunction: Vec<Complex64> =
+            x_vals.iter().map(|x| initial_wavefunction(*x)).collect();
+        wavefunction[0] = Complex64::new(0.0, 0.0);
+        wavefunction[steps - 1] = Complex64::new(0.0, 0.0);
+
+        Ok(CrankNicolsonPropagator {
+            config: config.clone(),
+            time: 0.0,
+            x_vals,
+            potential_vals,
+            absorption_vals,
+            lower,
+            diagonal,
+            upper,
+            wavefunction,
+        })
+    }
+
+    /// Advances the wavefunction by one time step.
+    pub fn step(&mut self) {
+        let steps = self.wavefunction.len();
+        let psi = &self.wavefunction;
+        let kinetic = 0.5 / (self.config.step_size * self.config.step_size);
+        let half_step = 0.5 * self.config.time_step;
+
+        // Apply 1 - iHΔt/2, with H including the absorbing potential -iW
+        let rhs: Vec<Complex64> = (1..steps - 1)
+            .map(|i| {
+                let diagonal = Complex64::new(
+                    1.0 - self.absorption_vals[i] * half_step,
+                    -(2.0 * kinetic + self.potential_vals[i]) * half_step,
+                );
+                diagonal * psi[i]
+                    + Complex64::new(0.0, kinetic * half_step) * (psi[i - 1] + psi[i + 1])
+            })
+            .collect();
+
+        let interior = solve_tridiagonal(&self.lower, &self.diagonal, &self.upper, &rhs);
+        self.wavefunction[1..steps - 1].copy_from_slice(&interior);
+
+        self.time += self.config.time_step;
+    }
+
+    /// Takes `time_steps` steps, recording a snapshot at the start and after every
+    /// `snapshot_interval` steps.
+    pub fn propagate(&mut self) -> Result<Vec<Snapshot>, Error> {
+        self.propagate_observed(&mut Silent)
+    }
+
+    /// Like `propagate`, but reports the energy and time after every step to
+    /// `observer`, which may stop the propagation with `Error::Cancelled`.
+    pub fn propagate_observed(
+        &mut self,
+        observer: &mut dyn Observer,
+    ) -> Result<Vec<Snapshot>, Error> {
+        let mut snapshots = vec![self.snapshot()];
+
+        for i in 1..=self.config.time_steps {
+            self.step();
+            let progress = Progress {
+                time: Some(self.time),
+                ..Progress::new(i - 1, self.energy())
+            };
+            if observer.observe(&progress) == Control::Stop {
+                return Err(Error::Cancelled { iterations: i });
+            }
+            if i % self.config.snapshot_interval == 0 {
+                let snapshot = self.snapshot();
+                if !snapshot.norm.is_finite() {
+                    return Err(Error::NumericalOverflow);
+                }
+                snapshots.push(snapshot);
+            }
+        }
+
+        Ok(snapshots)
+    }
+
+    /// Records the probability density, norm and energy at the current time.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            time: self.time,
+            probability_density: self
+                .x_vals
+                .iter()
+                .zip(self.wavefunction.iter())
+                .map(|(x, psi)| (*x, psi.norm_sqr()))
+                .collect(),
+            norm: self.norm(),
+            energy: self.energy(),
+        }
+    }
+
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    /// Returns ∫|ψ|²dx, which decreases as probability is absorbed at the edges.
+    pub fn norm(&self) -> f64 {
+        let density: Vec<f64> = self.wavefunction.iter().map(|psi| psi.norm_sqr()).collect();
+        trapezoidal(&density, &self.config.step_size)
+    }
+
+    /// Returns ⟨ψ|H|ψ⟩ / ⟨ψ|ψ⟩ for the finite difference Hamiltonian, without the
+    /// absorbing potential.
+    pub fn energy(&self) -> f64 {
+        let psi = &self.wavefunction;
+        let kinetic = 0.5 / (self.config.step_size * self.config.step_size);
+
+        let mut numerator = 0.0;
+        for i in 1..psi.len() - 1 {
+            let h_psi = (2.0 * kinetic + self.potential_vals[i]) * psi[i]
+                - kinetic * (psi[i - 1] + psi[i + 1]);
+            numerator += (psi[i].conj() * h_psi).re;
+        }
+
+        numerator / psi.iter().map(|psi| psi.norm_sqr()).sum::<f64>()
+    }
+
+    /// Returns a vector of (x, ψ) points for the wavefunction at the current time.
+    pub fn wavefunction_points(&self) -> Vec<(f64, Complex64)> {
+        self.x_vals
+            .iter()
+            .cloned()
+            .zip(self.wavefunction.iter().cloned())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::physics::dynamics::crank_nicolson::*;
+    use crate::physics::dynamics::gaussian_wavepacket;
+
+    #[test]
+    fn absorbing_boundary() {
+        let mut config = CrankNicolsonConfig {
+            x_min: -10.0,
+            x_max: 10.0,
+            step_size: 0.02,
+            time_step: 0.005,
+            time_steps: 800,
+            snapshot_interval: 800,
+            potential: |_: f64| 0.0,
+            absorbing_boundary: None,
+        };
+
+        // Without absorption the packet reflects off the walls and the evolution is unitary
+        let mut propagator =
+            CrankNicolsonPropagator::new(&config, gaussian_wavepacket(0.0, 0.5, 5.0)).unwrap();
+        let snapshots = propagator.propagate().unwrap();
+        assert!((snapshots[0].norm - 1.0).abs() < 1e-9);
+        assert!((snapshots[1].norm - 1.0).abs() < 1e-9);
+        assert!((snapshots[1].energy - snapshots[0].energy).abs() < 1e-9);
+
+        // With absorption the packet leaves the grid
+        config.absorbing_boundary = Some(AbsorbingBoundary {
+            width: 3.0,
+            strength: 10.0,
+        });
+        let mut propagator =
+            CrankNicolsonPropagator::new(&config, gaussian_wavepacket(0.0, 0.5, 5.0)).unwrap();
+        let snapshots = propagator.propagate().unwrap();
+        assert!(snapshots[1].norm < 1e-2);
+    }
+}
@@ -0,0 +1,260 @@
+//! Split-operator method for propagating a wavefunction in time. Each time step
+//! applies half a step of the potential in position space, a full step of the
+//! kinetic energy in momentum space (reached by FFT), and another half step of
+//! the potential. The grid is periodic with period `x_max - x_min`, and
+//! transforms are fastest when the number of grid points is a power of two.
+
+use num_complex::Complex64;
+
+use crate::error::Error;
+use crate::physics::dynamics::Snapshot;
+use crate::physics::potentials::Potential;
+use crate::physics::solvers::observer::{Control, Observer, Progress, Silent};
+use crate::utils::fourier::{fft, fft_wavenumbers, ifft};
+
+/// Configuration for the split-operator propagator
+#[derive(Clone)]
+pub struct SplitOperatorConfig<P> {
+    pub x_min: f64,
+    pub x_max: f64,
+    pub step_size: f64,
+    pub time_step: f64,
+    /// The number of time steps taken by `propagate`.
+    pub time_steps: usize,
+    /// The number of time steps between snapshots taken by `propagate`.
+    pub snapshot_interval: usize,
+    pub potential: P,
+}
+
+/// A propagator for the time-dependent Schrodinger equation using the
+/// split-operator method.
+pub struct SplitOperatorPropagator<P> {
+    pub config: SplitOperatorConfig<P>,
+    time: f64,
+    x_vals: Vec<f64>,
+    potential_vals: Vec<f64>,
+    wavenumbers: Vec<f64>,
+    potential_phase: Vec<Complex64>,
+    kinetic_phase: Vec<Complex64>,
+    wavefunction: Vec<Complex64>,
+}
+
+impl<P: Potential + Clone> SplitOperatorPropagator<P> {
+    /// Samples the initial wavefunction on the grid and precomputes the phase
+    /// factors exp(-iVΔt/2) and exp(-ik²Δt/2).
+    pub fn new(
+        config: &SplitOperatorConfig<P>,
+        initial_wavefunction: impl Fn(f64) -> Complex64,
+    ) -> Result<Self, Error> {
+        if config.step_size <= 0.0 || config.time_step <= 0.0 {
+            return Err(Error::InvalidConfig(
+                "step_size and time_step must be positive".to_string(),
+            ));
+        }
+        if config.x_min >= config.x_max {
+            return Err(Error::InvalidConfig(
+                "x_min must be less than x_max".to_string(),
+            ));
+        }
+        if config.snapshot_interval == 0 {
+            return Err(Error::InvalidConfig(
+                "snapshot_interval must be at least 1".to_string(),
+            ));
+        }
+
+        let points = ((config.x_max - config.x_min) / config.step_size).round() as usize;
+        if points < 2 {
+            return Err(Error::StepSizeTooLarge {
+                step_size: config.step_size,
+            });
+        }
+
+        let x_vals: Vec<f64> = (0..points)
+            .map(|i| config.x_min + (i as f64) * config.step_size)
+            .collect();
+        let potential_vals: Vec<f64> = x_vals.iter().map(|x| config.potential.value(*x)).collect();
+        let wavenumbers = fft_wavenumbers(points, config.step_size);
+
+        let potential_phase = potential_vals
+            .iter()
+            .map(|v| Complex64::from_polar(1.0, -0.5 * v * config.time_step))
+            .collect();
+        let kinetic_phase = wavenumbers
+            .iter()
+            .map(|k| Complex64::from_polar(1.0, -0.5 * k * k * config.time_step))
+            .collect();
+        let wavefunction = x_vals.iter().map(|x| initial_wavefunction(*x)).collect();
+
+        Ok(SplitOperatorPropagator {
+            config: config.clone(),
+            time: 0.0,
+            x_vals,
+            potential_vals,
+            wavenumbers,
+            potential_phase,
+            kinetic_phase,
+            wavefunction,
+        })
+    }
+
+    /// Advances the wavefunction by one time step.
+    pub fn step(&mut self) {
+        for (psi, phase) in self.wavefunction.iter_mut().zip(self.potential_phase.iter()) {
+            *psi *= phase;
+        }
+
+        let mut momentum_space = fft(&self.wavefunction);
+        for (phi, phase) in momentum_space.iter_mut().zip(self.kinetic_phase.iter()) {
+            *phi *= phase;
+        }
+        self.wavefunction = ifft(&momentum_space);
+
+        for (psi, phase) in self.wavefunction.iter_mut().zip(self.potential_phase.iter()) {
+            *psi *= phase;
+        }
+
+        self.time += self.config.time_step;
+    }
+
+    /// Takes `time_steps` steps, recording a snapshot at the start and after every
+    /// `snapshot_interval` steps.
+    pub fn propagate(&mut self) -> Result<Vec<Snapshot>, Error> {
+        self.propagate_observed(&mut Silent)
+    }
+
+    /// Like `propagate`, but reports the energy and time after every step to
+    /// `observer`, which may stop the propagation with `Error::Cancelled`.
+    pub fn propagate_observed(
+        &mut self,
+        observer: &mut dyn Observer,
+    ) -> Result<Vec<Snapshot>, Error> {
+        let mut snapshots = vec![self.snapshot()];
+
+        for i in 1..=self.config.time_steps {
+            self.step();
+            let progress = Progress {
+                time: Some(self.time),
+                ..Progress::new(i - 1, self.energy())
+            };
+            if observer.observe(&progress) == Control::Stop {
+                return Err(Error::Cancelled { iterations: i });
+            }
+            if i % self.config.snapshot_interval == 0 {
+                let snapshot = self.snapshot();
+                if !snapshot.norm.is_finite() {
+                    return Err(Error::NumericalOverflow);
+                }
+                snapshots.push(snapshot);
+            }
+        }
+
+        Ok(snapshots)
+    }
+
+    /// Records the probability density, norm and energy at the current time.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            time: self.time,
+            probability_density: self
+                .x_vals
+                .iter()
+                .zip(self.wavefunction.iter())
+                .map(|(x, psi)| (*x, psi.norm_sqr()))
+                .collect(),
+            norm: self.norm(),
+            energy: self.energy(),
+        }
+    }
+
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    /// Returns ∫|ψ|²dx. The grid is periodic, so the trapezoidal rule reduces to a sum.
+    pub fn norm(&self) -> f64 {
+        self.config.step_size * self.wavefunction.iter().map(|psi| psi.norm_sqr()).sum::<f64>()
+    }
+
+    /// Returns ⟨ψ|H|ψ⟩ / ⟨ψ|ψ⟩, with the kinetic energy evaluated in momentum space.
+    pub fn energy(&self) -> f64 {
+        let momentum_space = fft(&self.wavefunction);
+        let kinetic = momentum_space
+            .iter()
+            .zip(self.wavenumbers.iter())
+            .map(|(phi, k)| 0.5 * k * k * phi.norm_sqr())
+            .sum::<f64>()
+            / momentum_space.iter().map(|phi| phi.norm_sqr()).sum::<f64>();
+
+        let potential = self
+            .wavefunction
+            .iter()
+            .zip(self.potential_vals.iter())
+            .map(|(psi, v)| v * psi.norm_sqr())
+            .sum::<f64>()
+            / self.wavefunction.iter().map(|psi| psi.norm_sqr()).sum::<f64>();
+
+        kinetic + potential
+    }
+
+    /// Returns a vector of (x, ψ) points for the wavefunction at the current time.
+    pub fn wavefunction_points(&self) -> Vec<(f64, Complex64)> {
+        self.x_vals
+            .iter()
+            .cloned()
+            .zip(self.wavefunction.iter().cloned())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::physics::dynamics::gaussian_wavepacket;
+    use crate::physics::dynamics::split_operator::*;
+    use crate::physics::harmonic_potential;
+
+    #[test]
+    fn coherent_state_oscillates() {
+        let config = SplitOperatorConfig {
+            x_min: -8.0,
+            x_max: 8.0,
+            step_size: 16.0 / 256.0,
+            time_step: PI / 1000.0,
+            time_steps: 1000,
+            snapshot_interval: 500,
+            potential: harmonic_potential,
+        };
+        let x0 = 1.5;
+        let mut propagator =
+            SplitOperatorPropagator::new(&config, gaussian_wavepacket(x0, 0.5_f64.sqrt(), 0.0))
+                .unwrap();
+        let snapshots = propagator.propagate().unwrap();
+        assert_eq!(snapshots.len(), 3);
+
+        for snapshot in &snapshots {
+            assert!((snapshot.norm - 1.0).abs() < 1e-9);
+            assert!((snapshot.energy - (0.5 + 0.5 * x0 * x0)).abs() < 1e-5);
+        }
+
+        // After half a period the packet is mirrored to the other side
+        let mean_x: f64 = snapshots[2]
+            .probability_density
+            .iter()
+            .map(|(x, density)| x * density * config.step_size)
+            .sum();
+        assert!((mean_x + x0).abs() < 1e-3);
+
+        let mut times = Vec::new();
+        let result = propagator.propagate_observed(&mut |progress: &Progress| {
+            times.push(progress.time.unwrap());
+            if progress.iteration == 9 {
+                Control::Stop
+            } else {
+                Control::Continue
+            }
+        });
+        assert!(matches!(result, Err(Error::Cancelled { iterations: 10 })));
+        assert!((times[9] - times[0] - 9.0 * config.time_step).abs() < 1e-12);
+    }
+}
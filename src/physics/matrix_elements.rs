@@ -0,0 +1,148 @@
+//! Matrix elements ⟨ψ_m|Ô|ψ_n⟩ between two states, in units where ħ = m = 1.
+//! The operator acts on the ket, and the integral is taken over the ket's grid
+//! with the ket's quadrature rule and the bra linearly interpolated onto it, so
+//! the two states may come from different solvers or grids. Neither state needs to be normalized.
+
+use num_complex::Complex64;
+
+use crate::physics::potentials::Potential;
+use crate::physics::solvers::solution::Solution;
+use crate::utils::finite_difference::{first_derivative, second_derivative, SecondDerivateMethod};
+
+/// Returns ⟨bra|f⟩, where `f` holds the values of Ôψ on the ket's grid.
+fn element<B, K>(bra: &Solution<B>, ket: &Solution<K>, f: &[f64]) -> f64 {
+    let integrand: Vec<f64> = ket
+        .x
+        .iter()
+        .zip(f.iter())
+        .map(|(x, val)| bra.interpolate(*x) * val)
+        .collect();
+    ket.quadrature.integrate(&integrand, ket.step_size()) / (bra.norm() * ket.norm()).sqrt()
+}
+
+/// Returns ⟨bra|f(x)|ket⟩.
+pub fn function<B, K>(bra: &Solution<B>, ket: &Solution<K>, f: impl Fn(f64) -> f64) -> f64 {
+    let values: Vec<f64> = ket
+        .x
+        .iter()
+        .zip(ket.psi.iter())
+        .map(|(x, psi)| f(*x) * psi)
+        .collect();
+    element(bra, ket, &values)
+}
+
+/// Returns ⟨bra|ket⟩.
+pub fn overlap<B, K>(bra: &Solution<B>, ket: &Solution<K>) -> f64 {
+    function(bra, ket, |_| 1.0)
+}
+
+/// Returns ⟨bra|x|ket⟩, the transition dipole moment in units of the charge.
+pub fn position<B, K>(bra: &Solution<B>, ket: &Solution<K>) -> f64 {
+    function(bra, ket, |x| x)
+}
+
+/// Returns ⟨bra|p|ket⟩ = -i∫ψ_m ψ_n' dx, which is imaginary for real states.
+pub fn momentum<B, K>(bra: &Solution<B>, ket: &Solution<K>) -> Complex64 {
+    let steps = ket.psi.len();
+    let derivative: Vec<f64> = (0..steps)
+        .map(|i| {
+            first_derivative(&SecondDerivateMethod::at(i, steps), &ket.psi, i, ket.step_size())
+        })
+        .collect();
+    Complex64::new(0.0, -element(bra, ket, &derivative))
+}
+
+/// Returns ⟨bra|H|ket⟩ with H = -½d²/dx² + V(x).
+pub fn hamiltonian<B, K>(bra: &Solution<B>, ket: &Solution<K>, potential: &impl Potential) -> f64 {
+    let steps = ket.psi.len();
+    let values: Vec<f64> = (0..steps)
+        .map(|i| {
+            let method = SecondDerivateMethod::at(i, steps);
+            -0.5 * second_derivative(&method, &ket.psi, i, ket.step_size())
+                + potential.value(ket.x[i]) * ket.psi[i]
+        })
+        .collect();
+    element(bra, ket, &values)
+}
+
+/// Returns the oscillator strength 2(E_n - E_m)|⟨m|x|n⟩|² of the transition from
+/// `from` (m) to `to` (n). The strengths of all transitions out of a state sum to
+/// one (the Thomas-Reiche-Kuhn sum rule).
+pub fn oscillator_strength<B, K>(from: &Solution<B>, to: &Solution<K>) -> f64 {
+    let dipole = position(from, to);
+    2.0 * (to.energy - from.energy) * dipole * dipole
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::physics::harmonic_potential;
+    use crate::physics::matrix_elements::*;
+    use crate::physics::solvers::imaginary_time::{ImaginaryTimeConfig, ImaginaryTimeSolver};
+    use crate::physics::solvers::matching::{MatchingConfig, MatchingSolver};
+    use crate::physics::solvers::Solver;
+    use crate::utils::integration::Quadrature;
+
+    #[test]
+    fn harmonic_oscillator_selection_rules() {
+        let mut solver = ImaginaryTimeSolver::new(&ImaginaryTimeConfig {
+            x_min: -6.0,
+            x_max: 6.0,
+            step_size: 0.01,
+            potential: harmonic_potential,
+            time_step: 1.0,
+            num_states: 4,
+            tolerance: 1e-13,
+            max_iterations: 10000,
+            quadrature: Quadrature::Trapezoidal,
+        });
+        solver.solve().unwrap();
+        let states: Vec<Solution<()>> = (0..4)
+            .map(|n| Solution::new(&solver.wavefunction_points_of(n), solver.energies()[n], &()))
+            .collect();
+
+        // ⟨m|x|n⟩ = √(n/2) δ_{m,n-1} + √((n+1)/2) δ_{m,n+1}, and likewise for p
+        for m in 0..4 {
+            for n in 0..4 {
+                let expected = if m + 1 == n {
+                    (n as f64 / 2.0).sqrt()
+                } else if n + 1 == m {
+                    (m as f64 / 2.0).sqrt()
+                } else {
+                    0.0
+                };
+                assert!((position(&states[m], &states[n]).abs() - expected).abs() < 1e-4);
+                assert!((momentum(&states[m], &states[n]).norm() - expected).abs() < 1e-3);
+
+                let energy = if m == n { n as f64 + 0.5 } else { 0.0 };
+                let element = hamiltonian(&states[m], &states[n], &harmonic_potential);
+                assert!((element - energy).abs() < 1e-3);
+            }
+        }
+
+        // The whole sum rule comes from the n = 0 → 1 transition
+        assert!((oscillator_strength(&states[0], &states[1]) - 1.0).abs() < 1e-3);
+        assert!(oscillator_strength(&states[0], &states[2]).abs() < 1e-6);
+
+        // A state found by another solver on a different grid
+        let first_excited = MatchingSolver::new(&MatchingConfig {
+            x_min: -8.0,
+            x_max: 8.0,
+            x_match: 0.7,
+            step_size: 0.004,
+            initial_energy: 0.0,
+            initial_energy_step_size: 0.1,
+            energy_step_size_cutoff: 1e-10,
+            max_iterations: 10000,
+            potential: harmonic_potential,
+            using_numerov: true,
+            guarding_scale_factor: false,
+            quantum_number: Some(1),
+            quadrature: Quadrature::Trapezoidal,
+        })
+        .solve()
+        .unwrap();
+        assert!((overlap(&states[1], &first_excited).abs() - 1.0).abs() < 1e-4);
+        assert!(overlap(&states[0], &first_excited).abs() < 1e-6);
+        assert!((position(&first_excited, &states[0]).abs() - 0.5f64.sqrt()).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,127 @@
+//! The momentum space wavefunction φ(p) = (2π)^(-1/2) ∫ψ(x)exp(-ipx)dx of a
+//! wavefunction given as (x, ψ) points, such as the output of
+//! `Solver::wavefunction_points`, in units where ħ = 1.
+
+use std::f64::consts::PI;
+
+use num_complex::Complex64;
+
+use crate::utils::fourier::{fft, fft_wavenumbers};
+
+/// Returns (p, φ(p)) points in increasing order of p. The momentum grid has as
+/// many points as the position grid, spaced by 2π/(nΔx) where Δx is the average
+/// spacing of the positions, so it covers |p| <= π/Δx.
+///
+/// Uniform grids are transformed with an FFT. Otherwise the integral is evaluated
+/// directly with the trapezoidal rule at each momentum, which takes O(n²) time.
+pub fn momentum_wavefunction(points: &[(f64, f64)]) -> Vec<(f64, Complex64)> {
+    let n = points.len();
+    if n < 2 {
+        return Vec::new();
+    }
+
+    let x_min = points[0].0;
+    let step_size = (points[n - 1].0 - x_min) / (n - 1) as f64;
+    let scale = (2.0 * PI).sqrt().recip();
+
+    let mut pairs: Vec<(f64, Complex64)> = if is_uniform(points, step_size) {
+        let data: Vec<Complex64> = points
+            .iter()
+            .map(|(_, psi)| Complex64::new(*psi, 0.0))
+            .collect();
+
+        // The FFT sums from x_min, so the phase exp(-ipx_min) is restored here
+        fft_wavenumbers(n, step_size)
+            .into_iter()
+            .zip(fft(&data))
+            .map(|(p, val)| (p, val * step_size * scale * Complex64::from_polar(1.0, -p * x_min)))
+            .collect()
+    } else {
+        fft_wavenumbers(n, step_size)
+            .into_iter()
+            .map(|p| {
+                let integrand = |(x, psi): &(f64, f64)| Complex64::from_polar(*psi, -p * x);
+                let integral: Complex64 = points
+                    .windows(2)
+                    .map(|w| 0.5 * (w[1].0 - w[0].0) * (integrand(&w[0]) + integrand(&w[1])))
+                    .sum();
+                (p, integral * scale)
+            })
+            .collect()
+    };
+
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+    pairs
+}
+
+/// Returns (p, |φ(p)|²) points in increasing order of p. The density integrates
+/// to the same value as |ψ(x)|².
+pub fn momentum_density(points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    momentum_wavefunction(points)
+        .into_iter()
+        .map(|(p, phi)| (p, phi.norm_sqr()))
+        .collect()
+}
+
+/// Returns whether every spacing of the grid is within a tiny tolerance of
+/// `step_size`.
+fn is_uniform(points: &[(f64, f64)], step_size: f64) -> bool {
+    points
+        .windows(2)
+        .all(|w| ((w[1].0 - w[0].0) - step_size).abs() <= 1e-9 * step_size.abs())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::physics::harmonic_potential;
+    use crate::physics::momentum_space::*;
+    use crate::physics::solvers::matching::{MatchingConfig, MatchingSolver};
+    use crate::physics::solvers::Solver;
+    use crate::utils::integration::{trapezoidal, Quadrature};
+
+    #[test]
+    fn harmonic_oscillator_momentum_distribution() {
+        // The harmonic oscillator states are the same in momentum space, so
+        // |φ₁(p)|² = 2p²exp(-p²)/√π
+        let mut solver = MatchingSolver::new(&MatchingConfig {
+            x_min: -8.0,
+            x_max: 8.0,
+            x_match: 0.7,
+            step_size: 0.01,
+            initial_energy: 0.0,
+            initial_energy_step_size: 0.1,
+            energy_step_size_cutoff: 1e-10,
+            max_iterations: 10000,
+            potential: harmonic_potential,
+            using_numerov: true,
+            guarding_scale_factor: false,
+            quantum_number: Some(1),
+            quadrature: Quadrature::Trapezoidal,
+        });
+        solver.solve().unwrap();
+        let density = momentum_density(&solver.wavefunction_points());
+
+        assert_eq!(density.len(), solver.wavefunction_points().len());
+        assert!(density.windows(2).all(|w| w[0].0 < w[1].0));
+        let expected = |p: f64| 2.0 * p * p * (-p * p).exp() / PI.sqrt();
+        for (p, val) in density.iter() {
+            assert!((val - expected(*p)).abs() < 1e-4);
+        }
+        let step = density[1].0 - density[0].0;
+        let vals: Vec<f64> = density.iter().map(|(_, val)| *val).collect();
+        assert!((trapezoidal(&vals, &step) - 1.0).abs() < 1e-4);
+
+        // A grid that is denser near the origin is integrated directly
+        let points: Vec<(f64, f64)> = (0..=400)
+            .map(|i| {
+                let x = 2.0 * (-3.0 + 0.015 * i as f64).sinh();
+                (x, PI.powf(-0.25) * (-0.5 * x * x).exp())
+            })
+            .collect();
+        for (p, val) in momentum_density(&points) {
+            if p.abs() < 4.0 {
+                assert!((val - (-p * p).exp() / PI.sqrt()).abs() < 1e-4);
+            }
+        }
+    }
+}
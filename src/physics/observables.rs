@@ -0,0 +1,187 @@
+//! Expectation values of a real wavefunction given as (x, ψ) points on a uniform
+//! grid, such as the output of `Solver::wavefunction_points` or
+//! `Solution::points`. The wavefunction does not need to be normalized.
+//! Derivatives are second order finite differences and integrals use the given
+//! quadrature rule.
+
+use num_complex::Complex64;
+
+use crate::error::Error;
+use crate::physics::potentials::Potential;
+use crate::utils::finite_difference::{first_derivative, second_derivative, SecondDerivateMethod};
+use crate::utils::integration::Quadrature;
+
+/// Expectation values of a wavefunction, in units where ħ = m = 1.
+#[derive(Clone, Copy, Debug)]
+pub struct Observables {
+    /// ⟨x⟩
+    pub position: f64,
+    /// ⟨x²⟩
+    pub position_sqr: f64,
+    /// ⟨p⟩ = -i∫ψψ'dx. For a real ψ this is imaginary and vanishes when ψ vanishes
+    /// at both ends of the grid, so a nonzero value means the grid is too small.
+    pub momentum: Complex64,
+    /// ⟨p²⟩ = -∫ψψ''dx
+    pub momentum_sqr: f64,
+    /// ⟨T⟩ = ⟨p²⟩/2
+    pub kinetic_energy: f64,
+    /// ⟨V⟩
+    pub potential_energy: f64,
+    /// ⟨x dV/dx⟩, which equals 2⟨T⟩ for a bound state by the virial theorem.
+    pub virial: f64,
+}
+
+impl Observables {
+    /// Computes the expectation values of the wavefunction at `points` in
+    /// `potential`, integrating with `quadrature`. Returns an error if there are
+    /// fewer than three points.
+    pub fn new(
+        points: &[(f64, f64)],
+        potential: &impl Potential,
+        quadrature: Quadrature,
+    ) -> Result<Self, Error> {
+        let steps = points.len();
+        if steps < 3 {
+            return Err(Error::InvalidConfig(
+                "at least three points are needed".to_string(),
+            ));
+        }
+
+        let step_size = points[1].0 - points[0].0;
+        let (x, psi): (Vec<f64>, Vec<f64>) = points.iter().cloned().unzip();
+        let potential_vals: Vec<f64> = x.iter().map(|x| potential.value(*x)).collect();
+
+        let method = |i: usize| SecondDerivateMethod::at(i, steps);
+
+        // Returns ∫ψ f ψ dx / ∫|ψ|²dx for f given at each grid index
+        let density: Vec<f64> = psi.iter().map(|val| val * val).collect();
+        let norm = quadrature.integrate(&density, step_size);
+        let expectation = |f: &dyn Fn(usize) -> f64| {
+            let integrand: Vec<f64> = (0..steps).map(|i| psi[i] * f(i)).collect();
+            quadrature.integrate(&integrand, step_size) / norm
+        };
+
+        let momentum_sqr =
+            -expectation(&|i| second_derivative(&method(i), &psi, i, step_size));
+
+        Ok(Observables {
+            position: expectation(&|i| x[i] * psi[i]),
+            position_sqr: expectation(&|i| x[i] * x[i] * psi[i]),
+            momentum: Complex64::new(
+                0.0,
+                -expectation(&|i| first_derivative(&method(i), &psi, i, step_size)),
+            ),
+            momentum_sqr,
+            kinetic_energy: 0.5 * momentum_sqr,
+            potential_energy: expectation(&|i| potential_vals[i] * psi[i]),
+            virial: expectation(&|i| {
+                x[i] * first_derivative(&method(i), &potential_vals, i, step_size) * psi[i]
+            }),
+        })
+    }
+
+    /// Returns Δx = √(⟨x²⟩ - ⟨x⟩²).
+    pub fn position_uncertainty(&self) -> f64 {
+        (self.position_sqr - self.position * self.position).max(0.0).sqrt()
+    }
+
+    /// Returns Δp = √(⟨p²⟩ - |⟨p⟩|²).
+    pub fn momentum_uncertainty(&self) -> f64 {
+        (self.momentum_sqr - self.momentum.norm_sqr()).max(0.0).sqrt()
+    }
+
+    /// Returns Δx·Δp, which is at least 1/2 by the uncertainty principle.
+    pub fn uncertainty_product(&self) -> f64 {
+        self.position_uncertainty() * self.momentum_uncertainty()
+    }
+
+    /// Returns ⟨H⟩ = ⟨T⟩ + ⟨V⟩.
+    pub fn energy(&self) -> f64 {
+        self.kinetic_energy + self.potential_energy
+    }
+
+    /// Returns 2⟨T⟩ - ⟨x dV/dx⟩, which vanishes for a bound state by the virial
+    /// theorem. The theorem assumes that ψ vanishes well inside the grid.
+    pub fn virial_residual(&self) -> f64 {
+        2.0 * self.kinetic_energy - self.virial
+    }
+
+    /// Returns ⟨H⟩ - `energy`, which vanishes when the wavefunction is the
+    /// eigenstate a solver reported with `energy`.
+    pub fn energy_residual(&self, energy: f64) -> f64 {
+        self.energy() - energy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::physics::observables::*;
+    use crate::physics::solvers::matching::{MatchingConfig, MatchingSolver};
+    use crate::physics::solvers::Solver;
+    use crate::physics::{harmonic_potential, lennard_jones_potential};
+    use crate::utils::integration::Quadrature;
+
+    #[test]
+    fn harmonic_oscillator_observables() {
+        let mut solver = MatchingSolver::new(&MatchingConfig {
+            x_min: -8.0,
+            x_max: 8.0,
+            x_match: 0.7,
+            step_size: 0.01,
+            initial_energy: 0.0,
+            initial_energy_step_size: 0.1,
+            energy_step_size_cutoff: 1e-10,
+            max_iterations: 10000,
+            potential: harmonic_potential,
+            using_numerov: true,
+            guarding_scale_factor: false,
+            quantum_number: Some(1),
+            quadrature: Quadrature::Trapezoidal,
+        });
+        let solution = solver.solve().unwrap();
+        let observables =
+            Observables::new(&solution.points(), &harmonic_potential, solution.quadrature).unwrap();
+
+        // ⟨x²⟩ = ⟨p²⟩ = n + 1/2 and ⟨T⟩ = ⟨V⟩ = E/2
+        assert!(observables.position.abs() < 1e-6);
+        assert!(observables.momentum.norm() < 1e-6);
+        assert!((observables.position_sqr - 1.5).abs() < 1e-4);
+        assert!((observables.momentum_sqr - 1.5).abs() < 1e-4);
+        assert!((observables.kinetic_energy - observables.potential_energy).abs() < 1e-4);
+        assert!((observables.uncertainty_product() - 1.5).abs() < 1e-4);
+        assert!(observables.virial_residual().abs() < 1e-4);
+        assert!(observables.energy_residual(solver.energy()).abs() < 1e-4);
+
+        let mut solver = MatchingSolver::new(&MatchingConfig {
+            x_min: 0.5,
+            x_max: 6.0,
+            x_match: 1.1,
+            step_size: 0.001,
+            initial_energy: 0.0,
+            initial_energy_step_size: 0.1,
+            energy_step_size_cutoff: 1e-10,
+            max_iterations: 10000,
+            potential: lennard_jones_potential,
+            using_numerov: true,
+            guarding_scale_factor: false,
+            quantum_number: Some(0),
+            quadrature: Quadrature::Trapezoidal,
+        });
+        let solution = solver.solve().unwrap();
+        let observables =
+            Observables::new(&solution.points(), &lennard_jones_potential, solution.quadrature)
+                .unwrap();
+        assert!(observables.uncertainty_product() > 0.5);
+        assert!(observables.virial_residual().abs() < 1e-3);
+        assert!(observables.energy_residual(solver.energy()).abs() < 1e-3);
+
+        assert!(matches!(
+            Observables::new(
+                &solution.points()[..2],
+                &lennard_jones_potential,
+                Quadrature::Trapezoidal
+            ),
+            Err(Error::InvalidConfig(_))
+        ));
+    }
+}
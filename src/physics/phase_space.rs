@@ -0,0 +1,144 @@
+//! Phase space distributions of a real wavefunction given as (x, ψ) points on a
+//! uniform grid, such as the output of `MatchingSolver::wavefunction_points`, in
+//! units where ħ = 1. Both distributions are returned as arrays with one row for
+//! each grid point and one column for each requested momentum, and integrate to
+//! ∫|ψ|²dx over phase space.
+
+use std::f64::consts::PI;
+
+use ndarray::Array2;
+use num_complex::Complex64;
+
+/// Returns the Wigner quasi-probability distribution
+/// W(x, p) = (1/π) ∫ψ(x + y)ψ(x - y)exp(2ipy)dy at every grid point and each of
+/// `momenta`. Negative values have no classical counterpart. The integral over y
+/// is cut off where x ± y leaves the grid, and the cost is O(n²) per momentum.
+pub fn wigner(points: &[(f64, f64)], momenta: &[f64]) -> Array2<f64> {
+    let n = points.len();
+    let mut distribution = Array2::<f64>::zeros((n, momenta.len()));
+    if n < 2 {
+        return distribution;
+    }
+
+    let step_size = points[1].0 - points[0].0;
+    let psi: Vec<f64> = points.iter().map(|(_, psi)| *psi).collect();
+
+    for j in 0..n {
+        // ψ(x + y)ψ(x - y) is even in y for a real ψ, so only y >= 0 is summed
+        let reach = j.min(n - 1 - j);
+        let products: Vec<f64> = (0..=reach).map(|k| psi[j + k] * psi[j - k]).collect();
+
+        for (m, p) in momenta.iter().enumerate() {
+            let rotation = Complex64::from_polar(1.0, 2.0 * p * step_size);
+            let mut phase = rotation;
+            let mut sum = products[0];
+            for product in products.iter().skip(1) {
+                sum += 2.0 * product * phase.re;
+                phase *= rotation;
+            }
+            distribution[[j, m]] = step_size * sum / PI;
+        }
+    }
+
+    distribution
+}
+
+/// Returns the Husimi Q function Q(x, p) = |⟨x, p|ψ⟩|²/2π at every grid point
+/// and each of `momenta`, where |x, p⟩ is a Gaussian wavepacket of width `sigma`
+/// centered on x with momentum p. Unlike the Wigner distribution it is never
+/// negative. The wavepackets are cut off six widths from their centers.
+pub fn husimi(points: &[(f64, f64)], momenta: &[f64], sigma: f64) -> Array2<f64> {
+    let n = points.len();
+    let mut distribution = Array2::<f64>::zeros((n, momenta.len()));
+    if n < 2 {
+        return distribution;
+    }
+
+    let step_size = points[1].0 - points[0].0;
+    let reach = ((6.0 * sigma / step_size).ceil() as usize).min(n - 1);
+    let amplitude = (PI * sigma * sigma).powf(-0.25);
+
+    for j in 0..n {
+        let (x, _) = points[j];
+        let window = j.saturating_sub(reach)..(j + reach + 1).min(n);
+        let weighted: Vec<(f64, f64)> = points[window]
+            .iter()
+            .map(|(y, psi)| {
+                let u = (y - x) / sigma;
+                (*y, amplitude * (-0.5 * u * u).exp() * psi)
+            })
+            .collect();
+
+        for (m, p) in momenta.iter().enumerate() {
+            let rotation = Complex64::from_polar(1.0, -p * step_size);
+            let mut phase = Complex64::from_polar(1.0, -p * weighted[0].0);
+            let mut overlap = Complex64::new(0.0, 0.0);
+            for (_, val) in weighted.iter() {
+                overlap += val * phase;
+                phase *= rotation;
+            }
+            distribution[[j, m]] = (step_size * overlap).norm_sqr() / (2.0 * PI);
+        }
+    }
+
+    distribution
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::physics::harmonic_potential;
+    use crate::physics::phase_space::*;
+    use crate::physics::solvers::matching::{MatchingConfig, MatchingSolver};
+    use crate::physics::solvers::Solver;
+    use crate::utils::integration::Quadrature;
+
+    #[test]
+    fn harmonic_oscillator_distributions() {
+        let mut solver = MatchingSolver::new(&MatchingConfig {
+            x_min: -6.0,
+            x_max: 6.0,
+            x_match: 0.7,
+            step_size: 0.02,
+            initial_energy: 0.0,
+            initial_energy_step_size: 0.1,
+            energy_step_size_cutoff: 1e-10,
+            max_iterations: 10000,
+            potential: harmonic_potential,
+            using_numerov: true,
+            guarding_scale_factor: false,
+            quantum_number: Some(1),
+            quadrature: Quadrature::Trapezoidal,
+        });
+        solver.solve().unwrap();
+        let points = solver.wavefunction_points();
+        let momenta: Vec<f64> = (0..=80).map(|i| -4.0 + 0.1 * i as f64).collect();
+
+        // W₁ = (2(x² + p²) - 1)exp(-x² - p²)/π, which is -1/π at the origin
+        let distribution = wigner(&points, &momenta);
+        assert_eq!(distribution.dim(), (points.len(), momenta.len()));
+        for (j, (x, _)) in points.iter().enumerate() {
+            for (m, p) in momenta.iter().enumerate() {
+                let r_sqr = x * x + p * p;
+                let expected = (2.0 * r_sqr - 1.0) * (-r_sqr).exp() / PI;
+                assert!((distribution[[j, m]] - expected).abs() < 1e-3);
+            }
+        }
+
+        // Integrating over p gives back |ψ(x)|²
+        let j = points.iter().position(|(x, _)| (x - 1.0).abs() < 1e-6).unwrap();
+        let marginal = distribution.row(j).sum() * 0.1;
+        assert!((marginal - points[j].1 * points[j].1).abs() < 1e-3);
+
+        // With σ = 1 the wavepackets are the oscillator's coherent states, and
+        // Q₁ = (x² + p²)exp(-(x² + p²)/2)/4π
+        let distribution = husimi(&points, &momenta, 1.0);
+        for (j, (x, _)) in points.iter().enumerate() {
+            for (m, p) in momenta.iter().enumerate() {
+                let r_sqr = x * x + p * p;
+                let expected = r_sqr * (-0.5 * r_sqr).exp() / (4.0 * PI);
+                assert!(distribution[[j, m]] >= 0.0);
+                assert!((distribution[[j, m]] - expected).abs() < 1e-4);
+            }
+        }
+    }
+}
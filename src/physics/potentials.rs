@@ -0,0 +1,206 @@
+//! Potentials for the Schrodinger equation. Any closure or function taking x and
+//! returning V(x) is a potential, and the parameter structs below cover the
+//! potentials used throughout the crate. Potentials can be combined with
+//! `plus`, `scaled`, `shifted` and `translated`.
+
+/// The height used for walls that approximate an infinitely high potential.
+pub const WALL_HEIGHT: f64 = 1e5;
+
+pub trait Potential {
+    /// Returns the value of the potential at `x`.
+    fn value(&self, x: f64) -> f64;
+
+    /// Returns the potential V(x) + W(x).
+    fn plus<W: Potential>(self, other: W) -> Sum<Self, W>
+    where
+        Self: Sized,
+    {
+        Sum(self, other)
+    }
+
+    /// Returns the potential factor * V(x).
+    fn scaled(self, factor: f64) -> Scaled<Self>
+    where
+        Self: Sized,
+    {
+        Scaled {
+            potential: self,
+            factor,
+        }
+    }
+
+    /// Returns the potential V(x) + offset.
+    fn shifted(self, offset: f64) -> Shifted<Self>
+    where
+        Self: Sized,
+    {
+        Shifted {
+            potential: self,
+            offset,
+        }
+    }
+
+    /// Returns the potential V(x - distance), i.e. V moved along x by `distance`.
+    fn translated(self, distance: f64) -> Translated<Self>
+    where
+        Self: Sized,
+    {
+        Translated {
+            potential: self,
+            distance,
+        }
+    }
+}
+
+impl<F: Fn(f64) -> f64> Potential for F {
+    fn value(&self, x: f64) -> f64 {
+        self(x)
+    }
+}
+
+/// The harmonic oscillator potential ½ω²x² (for unit mass).
+#[derive(Clone, Copy, Debug)]
+pub struct HarmonicOscillator {
+    pub omega: f64,
+}
+
+impl Potential for HarmonicOscillator {
+    fn value(&self, x: f64) -> f64 {
+        0.5 * self.omega * self.omega * x * x
+    }
+}
+
+/// The Lennard-Jones potential 4ε((σ/x)¹² - (σ/x)⁶).
+#[derive(Clone, Copy, Debug)]
+pub struct LennardJones {
+    pub epsilon: f64,
+    pub sigma: f64,
+}
+
+impl Potential for LennardJones {
+    fn value(&self, x: f64) -> f64 {
+        4.0 * self.epsilon * ((self.sigma / x).powf(12.0) - (self.sigma / x).powf(6.0))
+    }
+}
+
+/// A finite square well of the given width centered at x = 0. The potential is
+/// -depth inside the well and zero outside.
+#[derive(Clone, Copy, Debug)]
+pub struct SquareWell {
+    pub width: f64,
+    pub depth: f64,
+}
+
+impl Potential for SquareWell {
+    fn value(&self, x: f64) -> f64 {
+        if x.abs() < 0.5 * self.width {
+            -self.depth
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Two wells separated by a central barrier and enclosed by walls of height
+/// `WALL_HEIGHT`. The barrier is centered at x = 0 and the potential is zero
+/// between the barrier and the walls.
+#[derive(Clone, Copy, Debug)]
+pub struct DoubleWell {
+    pub barrier_height: f64,
+    pub barrier_width: f64,
+    pub outer_width: f64,
+}
+
+impl Potential for DoubleWell {
+    fn value(&self, x: f64) -> f64 {
+        if x.abs() <= 0.5 * self.barrier_width {
+            self.barrier_height
+        } else if x.abs() < 0.5 * self.outer_width {
+            0.0
+        } else {
+            WALL_HEIGHT
+        }
+    }
+}
+
+/// The sum of two potentials, created by `Potential::plus`.
+#[derive(Clone, Copy, Debug)]
+pub struct Sum<V, W>(pub V, pub W);
+
+impl<V: Potential, W: Potential> Potential for Sum<V, W> {
+    fn value(&self, x: f64) -> f64 {
+        self.0.value(x) + self.1.value(x)
+    }
+}
+
+/// A potential multiplied by a constant, created by `Potential::scaled`.
+#[derive(Clone, Copy, Debug)]
+pub struct Scaled<V> {
+    pub potential: V,
+    pub factor: f64,
+}
+
+impl<V: Potential> Potential for Scaled<V> {
+    fn value(&self, x: f64) -> f64 {
+        self.factor * self.potential.value(x)
+    }
+}
+
+/// A potential offset by a constant energy, created by `Potential::shifted`.
+#[derive(Clone, Copy, Debug)]
+pub struct Shifted<V> {
+    pub potential: V,
+    pub offset: f64,
+}
+
+impl<V: Potential> Potential for Shifted<V> {
+    fn value(&self, x: f64) -> f64 {
+        self.potential.value(x) + self.offset
+    }
+}
+
+/// A potential moved along x, created by `Potential::translated`.
+#[derive(Clone, Copy, Debug)]
+pub struct Translated<V> {
+    pub potential: V,
+    pub distance: f64,
+}
+
+impl<V: Potential> Potential for Translated<V> {
+    fn value(&self, x: f64) -> f64 {
+        self.potential.value(x - self.distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::physics::potentials::*;
+    use crate::physics::{box_potential, double_well_potential, harmonic_potential, L};
+
+    #[test]
+    fn combinators() {
+        let well = SquareWell {
+            width: 2.0 * L,
+            depth: WALL_HEIGHT,
+        }
+        .shifted(WALL_HEIGHT);
+        for x in [-0.7, -0.5, -0.2, 0.0, 0.3, 0.5, 0.9] {
+            assert_eq!(well.value(x), box_potential(x));
+        }
+
+        let omega = 2.0;
+        let oscillator = HarmonicOscillator { omega }
+            .plus(|x: f64| x)
+            .translated(1.0);
+        assert_eq!(oscillator.value(3.0), 0.5 * omega * omega * 4.0 + 2.0);
+
+        let double_well = DoubleWell {
+            barrier_height: 100.0,
+            barrier_width: 0.2,
+            outer_width: 2.0,
+        }
+        .scaled(0.5);
+        assert_eq!(double_well.value(0.05), 0.5 * double_well_potential(0.05));
+        assert_eq!(harmonic_potential.scaled(2.0).value(3.0), 9.0);
+    }
+}
@@ -0,0 +1,212 @@
+//! Scattering off a potential in one dimension. The potential must be constant for
+//! x <= `x_min` and for x >= `x_max`. A wave of unit amplitude is sent in from the
+//! left, and the Schrodinger equation is integrated with Numerov's method from the
+//! transmitted wave on the right back to the left, where the solution is split into
+//! incident and reflected plane waves.
+
+use num_complex::Complex64;
+
+use crate::error::Error;
+use crate::physics::potentials::Potential;
+use crate::utils::finite_difference::numerov_step;
+
+/// Configuration for the scattering solver
+#[derive(Clone)]
+pub struct ScatteringConfig<P> {
+    pub x_min: f64,
+    pub x_max: f64,
+    pub step_size: f64,
+    pub potential: P,
+}
+
+/// The outcome of scattering at a single energy. The phases are those of the
+/// transmitted and reflected amplitudes relative to plane waves e^{±ikx}, which
+/// depend on the choice of origin.
+#[derive(Clone, Copy, Debug)]
+pub struct ScatteringCoefficients {
+    pub energy: f64,
+    pub transmission: f64,
+    pub reflection: f64,
+    pub transmission_phase: f64,
+    pub reflection_phase: f64,
+}
+
+/// A solver for transmission and reflection coefficients of a potential.
+pub struct ScatteringSolver<P> {
+    pub config: ScatteringConfig<P>,
+    steps: usize,
+}
+
+impl<P: Potential + Clone> ScatteringSolver<P> {
+    pub fn new(config: &ScatteringConfig<P>) -> Self {
+        let steps = ((config.x_max - config.x_min) / config.step_size).round() as usize + 1;
+        ScatteringSolver {
+            config: config.clone(),
+            steps,
+        }
+    }
+
+    /// Checks that the configuration describes a usable grid.
+    fn validate(&self) -> Result<(), Error> {
+        if self.config.step_size <= 0.0 {
+            return Err(Error::InvalidConfig(
+                "step_size must be positive".to_string(),
+            ));
+        }
+        if self.config.x_min >= self.config.x_max {
+            return Err(Error::InvalidConfig(
+                "x_min must be less than x_max".to_string(),
+            ));
+        }
+        if self.steps < 3 {
+            return Err(Error::StepSizeTooLarge {
+                step_size: self.config.step_size,
+            });
+        }
+        Ok(())
+    }
+
+    fn x_from_index(&self, i: usize) -> f64 {
+        self.config.x_min + (i as f64) * self.config.step_size
+    }
+
+    /// Computes the coefficients for a wave incident from the left with the given
+    /// energy, which must lie above the potential at `x_min`. Below the potential at
+    /// `x_max` the transmitted wave is evanescent and the transmission is zero.
+    pub fn coefficients(&self, energy: f64) -> Result<ScatteringCoefficients, Error> {
+        self.validate()?;
+
+        let h = self.config.step_size;
+        let potential_left = self.config.potential.value(self.config.x_min);
+        let potential_right = self.config.potential.value(self.config.x_max);
+        if energy <= potential_left {
+            return Err(Error::InvalidConfig(
+                "energy must lie above the potential at x_min".to_string(),
+            ));
+        }
+
+        let k_left = (2.0 * (energy - potential_left)).sqrt();
+        let k_sqr: Vec<f64> = (0..self.steps)
+            .map(|i| 2.0 * (energy - self.config.potential.value(self.x_from_index(i))))
+            .collect();
+
+        // The transmitted wave, with unit amplitude, at the two rightmost points
+        let transmitted = |x: f64| {
+            if energy > potential_right {
+                let k_right = (2.0 * (energy - potential_right)).sqrt();
+                Complex64::from_polar(1.0, k_right * x)
+            } else {
+                let kappa_right = (2.0 * (potential_right - energy)).sqrt();
+                Complex64::new((-kappa_right * (x - self.config.x_max)).exp(), 0.0)
+            }
+        };
+
+        // The equation is real, so the real and imaginary parts are integrated separately
+        let last = self.steps - 1;
+        let mut psi = vec![Complex64::new(0.0, 0.0); self.steps];
+        psi[last] = transmitted(self.x_from_index(last));
+        psi[last - 1] = transmitted(self.x_from_index(last - 1));
+        for i in (1..last).rev() {
+            let step = |psi_last: f64, psi_second_to_last: f64| {
+                numerov_step(
+                    psi_last,
+                    psi_second_to_last,
+                    k_sqr[i - 1],
+                    k_sqr[i],
+                    k_sqr[i + 1],
+                    h,
+                )
+            };
+            psi[i - 1] = Complex64::new(
+                step(psi[i].re, psi[i + 1].re),
+                step(psi[i].im, psi[i + 1].im),
+            );
+        }
+
+        if !psi[0].norm_sqr().is_finite() || !psi[1].norm_sqr().is_finite() {
+            return Err(Error::NumericalOverflow);
+        }
+
+        // Solve ψ = A e^{ikx} + B e^{-ikx} at the two leftmost points
+        let (x0, x1) = (self.x_from_index(0), self.x_from_index(1));
+        let determinant = Complex64::new(0.0, -2.0 * (k_left * h).sin());
+        let incident = (psi[0] * Complex64::from_polar(1.0, -k_left * x1)
+            - psi[1] * Complex64::from_polar(1.0, -k_left * x0))
+            / determinant;
+        let reflected = (psi[1] * Complex64::from_polar(1.0, k_left * x0)
+            - psi[0] * Complex64::from_polar(1.0, k_left * x1))
+            / determinant;
+
+        let transmission_amplitude = 1.0 / incident;
+        let reflection_amplitude = reflected / incident;
+        let transmission = if energy > potential_right {
+            let k_right = (2.0 * (energy - potential_right)).sqrt();
+            k_right / k_left * transmission_amplitude.norm_sqr()
+        } else {
+            0.0
+        };
+
+        Ok(ScatteringCoefficients {
+            energy,
+            transmission,
+            reflection: reflection_amplitude.norm_sqr(),
+            transmission_phase: transmission_amplitude.arg(),
+            reflection_phase: reflection_amplitude.arg(),
+        })
+    }
+
+    /// Computes the coefficients at each of the given energies.
+    pub fn spectrum(&self, energies: &[f64]) -> Result<Vec<ScatteringCoefficients>, Error> {
+        energies.iter().map(|energy| self.coefficients(*energy)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::physics::potentials::SquareWell;
+    use crate::physics::scattering::*;
+
+    #[test]
+    fn square_barrier() {
+        let (height, width) = (10.0, 1.0);
+        let config = ScatteringConfig {
+            x_min: -2.0,
+            x_max: 2.0,
+            step_size: 1e-4,
+            potential: SquareWell {
+                width,
+                depth: -height,
+            },
+        };
+        let solver = ScatteringSolver::new(&config);
+
+        for energy in [2.0, 8.0, 12.0, 30.0] {
+            let result = solver.coefficients(energy).unwrap();
+            assert!((result.transmission + result.reflection - 1.0).abs() < 1e-6);
+
+            let expected = if energy < height {
+                let kappa = (2.0 * (height - energy)).sqrt();
+                1.0 / (1.0
+                    + height * height * (kappa * width).sinh().powi(2)
+                        / (4.0 * energy * (height - energy)))
+            } else {
+                let k = (2.0 * (energy - height)).sqrt();
+                1.0 / (1.0
+                    + height * height * (k * width).sin().powi(2)
+                        / (4.0 * energy * (energy - height)))
+            };
+            assert!((result.transmission - expected).abs() < 1e-3 * expected.max(1e-2));
+        }
+
+        // A step that the particle cannot climb reflects everything
+        let step = ScatteringSolver::new(&ScatteringConfig {
+            x_min: -2.0,
+            x_max: 2.0,
+            step_size: 1e-4,
+            potential: |x: f64| if x > 0.0 { 5.0 } else { 0.0 },
+        });
+        let result = step.coefficients(3.0).unwrap();
+        assert_eq!(result.transmission, 0.0);
+        assert!((result.reflection - 1.0).abs() < 1e-6);
+    }
+}
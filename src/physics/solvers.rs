@@ -1,15 +1,52 @@
 use std::io::Write;
 
+use crate::error::Error;
+use crate::physics::solvers::observer::{Observer, Progress};
+use crate::physics::solvers::solution::Solution;
+use crate::utils::count_nodes;
+
 pub mod shooting;
 pub mod matching;
 pub mod variational;
+pub mod diagonalization;
+pub mod radial;
+pub mod parametric;
+pub mod variational_monte_carlo;
+pub mod diffusion_monte_carlo;
+pub mod imaginary_time;
+pub mod observer;
+pub mod solution;
+
+/// An energy level together with its wavefunction, as found by `Solver::solve_states`.
+#[derive(Clone, Debug)]
+pub struct Eigenpair {
+    pub energy: f64,
+    /// The number of nodes of the wavefunction.
+    pub nodes: usize,
+    /// The (x, ψ) points of the wavefunction.
+    pub wavefunction: Vec<(f64, f64)>,
+}
 
 pub trait Solver {
-    type CONFIG;
+    type CONFIG: Clone;
 
     fn new(config: &Self::CONFIG) -> Self;
 
-    fn solve(&mut self);
+    /// Searches for a solution of the Schrodinger equation and returns it. Returns
+    /// an error if the configuration is invalid or the search fails.
+    fn solve(&mut self) -> Result<Solution<Self::CONFIG>, Error>;
+
+    /// Like `solve`, but reports each iteration to `observer`, which may stop the
+    /// search with `Error::Cancelled`. Solvers without iterations to report only
+    /// report the final energy.
+    fn solve_observed(
+        &mut self,
+        observer: &mut dyn Observer,
+    ) -> Result<Solution<Self::CONFIG>, Error> {
+        let solution = self.solve()?;
+        observer.observe(&Progress::new(0, solution.energy));
+        Ok(solution)
+    }
 
     fn energy(&self) -> f64;
 
@@ -19,6 +56,138 @@ pub trait Solver {
     /// Returns a vector of (x, ψ) points for the wavefunction.
     fn wavefunction_points(&self) -> Vec<(f64, f64)>;
 
+    /// Returns an energy below every level the solver can find, typically the
+    /// minimum of the potential on the grid. Bracketing of levels starts here.
+    /// Solvers without such a bound return negative infinity, for which no level
+    /// can be bracketed.
+    fn energy_lower_bound(&self) -> f64 {
+        f64::NEG_INFINITY
+    }
+
+    /// Checks that the configuration is usable, returning the error `solve` would
+    /// return for it. `bracket_level` and `solve_states` call this before
+    /// integrating any trial wavefunction.
+    fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Integrates the wavefunction at a fixed trial energy, without searching for
+    /// an eigenvalue, and returns the ψ values. By the node theorem, the number of
+    /// nodes grows by one each time `energy` passes a level. Solvers that cannot
+    /// integrate at a fixed energy return `None`.
+    fn trial_wavefunction(&mut self, _energy: f64) -> Option<Vec<f64>> {
+        None
+    }
+
+    /// Sets the energy and energy step size that the next call to `solve` starts
+    /// from. A zero step size makes `solve` compute the wavefunction at exactly
+    /// `energy`.
+    fn set_initial_energy(&mut self, _energy: f64, _energy_step_size: f64) {}
+
+    /// The width below which a bracketed level is considered converged.
+    fn energy_tolerance(&self) -> f64 {
+        1e-10
+    }
+
+    /// Finds an energy interval `(lower, upper)`, no wider than `energy_tolerance`,
+    /// such that the trial wavefunction has at most `nodes` nodes at `lower` and
+    /// more than `nodes` nodes at `upper`. The interval therefore contains the
+    /// level whose wavefunction has `nodes` nodes.
+    fn bracket_level(&mut self, nodes: usize) -> Result<(f64, f64), Error> {
+        let count_nodes_at = |solver: &mut Self, energy: f64| {
+            solver
+                .trial_wavefunction(energy)
+                .map(|psi| count_nodes(&psi))
+                .ok_or(Error::LevelNotFound { nodes })
+        };
+
+        self.validate()?;
+        let mut lower = self.energy_lower_bound();
+        if !lower.is_finite() || count_nodes_at(self, lower)? > nodes {
+            return Err(Error::LevelNotFound { nodes });
+        }
+
+        let mut width = lower.abs().max(1.0);
+        let mut upper = lower + width;
+        while count_nodes_at(self, upper)? <= nodes {
+            lower = upper;
+            width *= 2.0;
+            upper = lower + width;
+            if !upper.is_finite() {
+                return Err(Error::LevelNotFound { nodes });
+            }
+        }
+
+        while upper - lower > self.energy_tolerance() {
+            let middle = 0.5 * (lower + upper);
+            if middle <= lower || middle >= upper {
+                break;
+            }
+            if count_nodes_at(self, middle)? <= nodes {
+                lower = middle;
+            } else {
+                upper = middle;
+            }
+        }
+
+        Ok((lower, upper))
+    }
+
+    /// Solves for the level with `nodes` nodes inside the energy interval
+    /// `(lower, upper)` found by `bracket_level`, ignoring any quantum number in the
+    /// configuration. The width of the interval is reported as the energy error.
+    fn solve_from_bracket(
+        &mut self,
+        nodes: usize,
+        (lower, upper): (f64, f64),
+    ) -> Result<Solution<Self::CONFIG>, Error> {
+        self.set_initial_energy(0.5 * (lower + upper), 0.0);
+        let mut solution = self.solve()?;
+        solution.quantum_number = Some(nodes);
+        solution.convergence.energy_error = Some(upper - lower);
+        Ok(solution)
+    }
+
+    /// Finds the `n` lowest levels, sorted by energy. Each level is bracketed by
+    /// counting the nodes of the trial wavefunction and then passed to
+    /// `solve_from_bracket`. Fewer than `n` levels are returned if they cannot all
+    /// be bracketed.
+    fn solve_states(&mut self, n: usize) -> Result<Vec<Eigenpair>, Error> {
+        self.validate()?;
+        let mut states = Vec::with_capacity(n);
+
+        let lower = self.energy_lower_bound();
+        if !lower.is_finite() {
+            return Err(Error::LevelNotFound { nodes: 0 });
+        }
+        let mut nodes = match self.trial_wavefunction(lower) {
+            Some(psi) => count_nodes(&psi),
+            None => return Ok(states),
+        };
+
+        while states.len() < n {
+            let (lower, upper) = match self.bracket_level(nodes) {
+                Ok(bracket) => bracket,
+                Err(Error::LevelNotFound { .. }) => break,
+                Err(err) => return Err(err),
+            };
+
+            let solution = self.solve_from_bracket(nodes, (lower, upper))?;
+            states.push(Eigenpair {
+                energy: solution.energy,
+                nodes,
+                wavefunction: solution.points(),
+            });
+
+            nodes = match self.trial_wavefunction(upper) {
+                Some(psi) => count_nodes(&psi),
+                None => break,
+            };
+        }
+
+        Ok(states)
+    }
+
     /// Prints energy and wavefunction data to a text file. Useful for later analysis
     /// with tools like gnuplot. The first line is the a '#' (gnuplot comment) followed
     /// by the energy value (e.g "# 1.24345678"). The rest of the lines in the file
@@ -34,6 +203,7 @@ pub trait Solver {
     ///     -1.1 -0.000009935227934806336
     ///       ︙             ︙
     /// ```
+    #[deprecated(note = "use `Solution::write_data` on the solution returned by `solve`")]
     fn dump_to_file(&mut self, data_file: &mut std::fs::File) -> Result<(), std::io::Error> {
         writeln!(data_file, "# {}", self.energy())?;
 
@@ -0,0 +1,213 @@
+//! Direct diagonalization of the finite difference Hamiltonian for solving the
+//! time-independent Schrodinger equation in one dimension. Unlike the matching
+//! and shooting methods, no initial energy guess is needed and the lowest
+//! `num_states` levels are found at once.
+
+use ndarray::Array2;
+use ndarray_linalg::{Eigh, UPLO};
+
+use crate::error::Error;
+use crate::physics::potentials::Potential;
+use crate::physics::solvers::solution::Solution;
+use crate::physics::solvers::{Eigenpair, Solver};
+
+/// Configuration for the diagonalization solver
+#[derive(Clone)]
+pub struct DiagonalizationConfig<P> {
+    pub x_min: f64,
+    pub x_max: f64,
+    pub step_size: f64,
+    pub potential: P,
+    pub num_states: usize,
+}
+
+/// A solver that finds the lowest `num_states` solutions by diagonalizing the
+/// Hamiltonian matrix. The wavefunction is taken to vanish at `x_min` and `x_max`.
+pub struct DiagonalizationSolver<P> {
+    pub config: DiagonalizationConfig<P>,
+    steps: usize,
+    energies: Vec<f64>,
+    wavefunctions: Vec<Vec<f64>>,
+}
+
+impl<P: Potential + Clone> DiagonalizationSolver<P> {
+    /// Returns the energies of the solved states in increasing order.
+    pub fn energies(&self) -> &[f64] {
+        &self.energies
+    }
+
+    /// Returns a vector of (x, ψ) points for the `n`-th solved state, where `n = 0`
+    /// is the ground state.
+    pub fn wavefunction_points_of(&self, n: usize) -> Vec<(f64, f64)> {
+        let mut pairs: Vec<(f64, f64)> = Vec::with_capacity(self.steps);
+
+        if let Some(wavefunction) = self.wavefunctions.get(n) {
+            for (i, psi_val) in wavefunction.iter().enumerate() {
+                pairs.push((self.config.x_min + (i as f64) * self.config.step_size, *psi_val));
+            }
+        }
+
+        pairs
+    }
+
+    /// Builds the Hamiltonian on the interior grid points and diagonalizes it,
+    /// keeping the lowest `num_states` levels. The eigenvectors are padded with the
+    /// boundary zeros and normalized so that ∫|ψ|²dx = 1.
+    fn diagonalize(&mut self, num_states: usize) -> Result<(), Error> {
+        self.energies.clear();
+        self.wavefunctions.clear();
+
+        if self.config.step_size <= 0.0 {
+            return Err(Error::InvalidConfig("step_size must be positive".to_string()));
+        }
+        if self.config.x_min >= self.config.x_max {
+            return Err(Error::InvalidConfig("x_min must be less than x_max".to_string()));
+        }
+        if self.steps < 3 {
+            return Err(Error::StepSizeTooLarge {
+                step_size: self.config.step_size,
+            });
+        }
+
+        let hamiltonian = hamiltonian_matrix(
+            self.steps,
+            self.config.step_size,
+            &self.config.potential,
+            self.config.x_min,
+        );
+        let (eigenvalues, eigenvectors) = hamiltonian.eigh(UPLO::Lower)?;
+
+        let scale = self.config.step_size.sqrt().recip();
+        for n in 0..num_states.min(eigenvalues.len()) {
+            let mut wavefunction = Vec::with_capacity(self.steps);
+            wavefunction.push(0.0);
+            wavefunction.extend(eigenvectors.column(n).iter().map(|val| val * scale));
+            wavefunction.push(0.0);
+
+            self.energies.push(eigenvalues[n]);
+            self.wavefunctions.push(wavefunction);
+        }
+
+        Ok(())
+    }
+}
+
+impl<P: Potential + Clone> Solver for DiagonalizationSolver<P> {
+    type CONFIG = DiagonalizationConfig<P>;
+
+    fn new(config: &Self::CONFIG) -> Self {
+        let steps = ((config.x_max - config.x_min) / config.step_size).round() as usize + 1;
+
+        DiagonalizationSolver {
+            config: config.clone(),
+            steps,
+            energies: Vec::with_capacity(config.num_states),
+            wavefunctions: Vec::with_capacity(config.num_states),
+        }
+    }
+
+    /// Returns the ground state. The other levels are available from
+    /// `energies` and `wavefunction_points_of`.
+    fn solve(&mut self) -> Result<Solution<Self::CONFIG>, Error> {
+        self.diagonalize(self.config.num_states)?;
+        Ok(Solution {
+            quantum_number: Some(0),
+            ..Solution::new(&self.wavefunction_points(), self.energy(), &self.config)
+        })
+    }
+
+    /// Returns the ground state energy, or NaN if the solver has not been run.
+    fn energy(&self) -> f64 {
+        self.energies.first().copied().unwrap_or(f64::NAN)
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(&self.config);
+    }
+
+    /// Returns a vector of (x, ψ) points for the ground state wavefunction.
+    fn wavefunction_points(&self) -> Vec<(f64, f64)> {
+        self.wavefunction_points_of(0)
+    }
+
+    fn energy_lower_bound(&self) -> f64 {
+        (0..self.steps)
+            .map(|i| {
+                self.config
+                    .potential
+                    .value(self.config.x_min + (i as f64) * self.config.step_size)
+            })
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Diagonalizes once for all `n` levels. The `n`-th eigenvector of the
+    /// tridiagonal Hamiltonian has exactly `n` nodes.
+    fn solve_states(&mut self, n: usize) -> Result<Vec<Eigenpair>, Error> {
+        self.diagonalize(n)?;
+
+        Ok((0..self.energies.len())
+            .map(|i| Eigenpair {
+                energy: self.energies[i],
+                nodes: i,
+                wavefunction: self.wavefunction_points_of(i),
+            })
+            .collect())
+    }
+}
+
+/// Assembles the Hamiltonian acting on the interior points of the grid as a
+/// symmetric tridiagonal matrix. This is the operator that
+/// `variational::hamiltonian_on_wavefunction` applies using the central
+/// difference, with ψ = 0 at both ends of the grid.
+pub fn hamiltonian_matrix(
+    steps: usize,
+    step_size: f64,
+    potential: &impl Potential,
+    x_min: f64,
+) -> Array2<f64> {
+    let interior = steps.saturating_sub(2);
+    let mut hamiltonian = Array2::<f64>::zeros((interior, interior));
+    let kinetic = 0.5 / (step_size * step_size);
+
+    for i in 0..interior {
+        hamiltonian[[i, i]] = 2.0 * kinetic + potential.value(x_min + ((i + 1) as f64) * step_size);
+        if i > 0 {
+            hamiltonian[[i, i - 1]] = -kinetic;
+            hamiltonian[[i - 1, i]] = -kinetic;
+        }
+    }
+
+    hamiltonian
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::physics::harmonic_potential;
+    use crate::physics::solvers::diagonalization::*;
+    use crate::utils::integration::trapezoidal;
+
+    #[test]
+    fn harmonic_oscillator_levels() {
+        let config = DiagonalizationConfig {
+            x_min: -6.0,
+            x_max: 6.0,
+            step_size: 0.02,
+            potential: harmonic_potential,
+            num_states: 4,
+        };
+        let mut solver = DiagonalizationSolver::new(&config);
+        solver.solve().unwrap();
+
+        assert_eq!(solver.energies().len(), 4);
+        for (n, energy) in solver.energies().iter().enumerate() {
+            assert!((energy - (n as f64 + 0.5)).abs() < 1e-3);
+        }
+
+        let f: Vec<f64> = solver
+            .wavefunction_points_of(2)
+            .iter()
+            .map(|(_, psi)| psi * psi)
+            .collect();
+        assert!((trapezoidal(&f, &config.step_size) - 1.0).abs() < 1e-6);
+    }
+}
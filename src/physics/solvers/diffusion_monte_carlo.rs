@@ -0,0 +1,274 @@
+//! Diffusion Monte Carlo for finding the ground state. A population of walkers
+//! diffuses in imaginary time, and each walker branches or dies according to
+//! exp(-(V - E_ref)Δt), with V averaged over the move. The reference energy E_ref
+//! is adjusted to keep the population near its target size. In the long time
+//! limit the walkers are distributed like the ground state ψ₀, and the average
+//! potential over the walkers estimates the ground state energy.
+
+use rand::distributions::StandardNormal;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::error::Error;
+use crate::physics::potentials::Potential;
+use crate::physics::solvers::observer::{Control, Observer, Progress, Silent};
+use crate::physics::solvers::solution::{Convergence, Solution};
+use crate::physics::solvers::Solver;
+use crate::utils::integration::Quadrature;
+use crate::utils::statistics::{blocking_error, mean, Histogram};
+
+/// The most copies a single walker can make in one time step, which keeps the
+/// population from exploding where the potential is far below the reference energy.
+const MAX_COPIES: usize = 3;
+
+#[derive(Clone)]
+pub struct DiffusionMonteCarloConfig<P> {
+    /// The grid of the wavefunction histogram. The walkers are not restricted to it.
+    pub x_min: f64,
+    pub x_max: f64,
+    pub step_size: f64,
+    pub potential: P,
+    /// The position that every walker starts from.
+    pub initial_position: f64,
+    pub target_walkers: usize,
+    pub time_step: f64,
+    /// The number of time steps taken before the energy and histogram are recorded.
+    pub thermalization_steps: usize,
+    pub time_steps: usize,
+    /// The number of consecutive time steps averaged together when estimating the
+    /// statistical error.
+    pub block_size: usize,
+    pub seed: u64,
+    /// The rule used to normalize the histogram.
+    pub quadrature: Quadrature,
+}
+
+/// A solver that finds the ground state with diffusion Monte Carlo.
+pub struct DiffusionMonteCarloSolver<P> {
+    pub config: DiffusionMonteCarloConfig<P>,
+    steps: usize,
+    energy: f64,
+    error: f64,
+    wavefunction: Vec<f64>,
+}
+
+impl<P: Potential + Clone> DiffusionMonteCarloSolver<P> {
+    /// Returns the statistical error (one standard deviation) of the energy.
+    pub fn error(&self) -> f64 {
+        self.error
+    }
+
+    fn x_from_index(&self, i: usize) -> f64 {
+        self.config.x_min + (i as f64) * self.config.step_size
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        let config = &self.config;
+        if config.step_size <= 0.0 || config.time_step <= 0.0 {
+            return Err(Error::InvalidConfig(
+                "step_size and time_step must be positive".to_string(),
+            ));
+        }
+        if config.x_min >= config.x_max {
+            return Err(Error::InvalidConfig(
+                "x_min must be less than x_max".to_string(),
+            ));
+        }
+        if config.target_walkers == 0 || config.time_steps == 0 || config.block_size == 0 {
+            return Err(Error::InvalidConfig(
+                "target_walkers, time_steps and block_size must be at least 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the average potential over the walkers.
+    fn average_potential(&self, walkers: &[f64]) -> f64 {
+        walkers
+            .iter()
+            .map(|x| self.config.potential.value(*x))
+            .sum::<f64>()
+            / walkers.len() as f64
+    }
+}
+
+impl<P: Potential + Clone> Solver for DiffusionMonteCarloSolver<P> {
+    type CONFIG = DiffusionMonteCarloConfig<P>;
+
+    fn new(config: &Self::CONFIG) -> Self {
+        let steps = ((config.x_max - config.x_min) / config.step_size).round() as usize + 1;
+        DiffusionMonteCarloSolver {
+            config: config.clone(),
+            steps,
+            energy: f64::NAN,
+            error: f64::NAN,
+            wavefunction: Vec::new(),
+        }
+    }
+
+    /// Evolves the walkers and records the energy and a histogram of the walker
+    /// positions after thermalization. The same seed always gives the same result.
+    fn solve(&mut self) -> Result<Solution<Self::CONFIG>, Error> {
+        self.solve_observed(&mut Silent)
+    }
+
+    /// Reports the average potential over the walkers, the number of walkers and
+    /// the reference energy after every time step.
+    fn solve_observed(
+        &mut self,
+        observer: &mut dyn Observer,
+    ) -> Result<Solution<Self::CONFIG>, Error> {
+        self.validate()?;
+
+        let mut rng = StdRng::seed_from_u64(self.config.seed);
+        let time_step = self.config.time_step;
+        let target_walkers = self.config.target_walkers as f64;
+
+        let mut walkers = vec![self.config.initial_position; self.config.target_walkers];
+        let mut reference_energy = self.average_potential(&walkers);
+        let mut energies = Vec::with_capacity(self.config.time_steps);
+        let mut histogram = Histogram::new(
+            self.config.x_min - 0.5 * self.config.step_size,
+            self.config.step_size,
+            self.steps,
+        );
+
+        let total_steps = self.config.thermalization_steps + self.config.time_steps;
+        for i in 0..total_steps {
+            let mut next_walkers = Vec::with_capacity(2 * walkers.len());
+            for x in walkers.iter() {
+                let moved = x + time_step.sqrt() * rng.sample::<f64, _>(StandardNormal);
+                let potential =
+                    0.5 * (self.config.potential.value(*x) + self.config.potential.value(moved));
+                let weight = (-(potential - reference_energy) * time_step).exp();
+                let copies = ((weight + rng.gen::<f64>()) as usize).min(MAX_COPIES);
+                for _ in 0..copies {
+                    next_walkers.push(moved);
+                }
+            }
+            walkers = next_walkers;
+
+            if walkers.is_empty() {
+                return Err(Error::NonConvergence { iterations: i + 1 });
+            }
+
+            // Feed back the population size so that it stays near the target
+            let average_potential = self.average_potential(&walkers);
+            reference_energy =
+                average_potential + (target_walkers / walkers.len() as f64).ln() / time_step;
+
+            if i >= self.config.thermalization_steps {
+                energies.push(average_potential);
+                histogram.add(&walkers);
+            }
+
+            let progress = Progress {
+                walkers: Some(walkers.len()),
+                reference_energy: Some(reference_energy),
+                ..Progress::new(i, average_potential)
+            };
+            if observer.observe(&progress) == Control::Stop {
+                return Err(Error::Cancelled { iterations: i + 1 });
+            }
+        }
+
+        self.energy = mean(&energies);
+        self.error = blocking_error(&energies, self.config.block_size);
+        if !self.energy.is_finite() {
+            return Err(Error::NumericalOverflow);
+        }
+
+        // The walkers are distributed like ψ₀ rather than |ψ₀|², so the histogram is
+        // normalized so that its square integrates to one.
+        let density = histogram.density();
+        let density_sqr: Vec<f64> = density.iter().map(|val| val * val).collect();
+        let norm = self.config.quadrature.integrate(&density_sqr, self.config.step_size).sqrt();
+        self.wavefunction = density.iter().map(|val| val / norm).collect();
+
+        Ok(Solution {
+            quantum_number: Some(0),
+            convergence: Convergence {
+                iterations: total_steps,
+                energy_error: Some(self.error),
+            },
+            quadrature: self.config.quadrature,
+            ..Solution::new(&self.wavefunction_points(), self.energy, &self.config)
+        })
+    }
+
+    fn energy(&self) -> f64 {
+        self.energy
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(&self.config);
+    }
+
+    /// Returns the histogram estimate of the ground state, with one bin centered on
+    /// each grid point.
+    fn wavefunction_points(&self) -> Vec<(f64, f64)> {
+        self.wavefunction
+            .iter()
+            .enumerate()
+            .map(|(i, psi)| (self.x_from_index(i), *psi))
+            .collect()
+    }
+
+    fn energy_lower_bound(&self) -> f64 {
+        (0..self.steps)
+            .map(|i| self.config.potential.value(self.x_from_index(i)))
+            .fold(f64::INFINITY, f64::min)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::physics::harmonic_potential;
+    use crate::physics::solvers::diffusion_monte_carlo::*;
+
+    #[test]
+    fn harmonic_oscillator_ground_state() {
+        let config = DiffusionMonteCarloConfig {
+            x_min: -4.0,
+            x_max: 4.0,
+            step_size: 0.2,
+            potential: harmonic_potential,
+            initial_position: 0.0,
+            target_walkers: 500,
+            time_step: 0.01,
+            thermalization_steps: 500,
+            time_steps: 4000,
+            block_size: 200,
+            seed: 7,
+            quadrature: Quadrature::Trapezoidal,
+        };
+        let mut solver = DiffusionMonteCarloSolver::new(&config);
+        solver.solve().unwrap();
+
+        assert!(solver.error() > 0.0 && solver.error() < 0.01);
+        assert!((solver.energy() - 0.5).abs() < 4.0 * solver.error() + 0.01);
+
+        // Compare the histogram with ψ₀ = π^(-1/4) exp(-x²/2)
+        let psi_0 = |x: f64| std::f64::consts::PI.powf(-0.25) * (-0.5 * x * x).exp();
+        for (x, psi) in solver.wavefunction_points() {
+            assert!((psi - psi_0(x)).abs() < 0.05);
+        }
+
+        // The same seed replays the same run
+        let energy = solver.energy();
+        solver.reset();
+        solver.solve().unwrap();
+        assert_eq!(solver.energy(), energy);
+
+        let result = solver.solve_observed(&mut |progress: &Progress| {
+            assert!(progress.walkers.unwrap() > 0);
+            assert!(progress.reference_energy.unwrap().is_finite());
+            if progress.iteration == 9 {
+                Control::Stop
+            } else {
+                Control::Continue
+            }
+        });
+        assert!(matches!(result, Err(Error::Cancelled { iterations: 10 })));
+    }
+}
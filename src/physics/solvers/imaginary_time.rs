@@ -0,0 +1,338 @@
+//! Imaginary-time propagation for finding the lowest levels. Evolving a state in
+//! imaginary time multiplies each eigenstate component by exp(-E_nτ), so after
+//! renormalizing, the lowest level present in the state dominates. Excited states
+//! are reached by orthogonalizing against the levels already found (Gram-Schmidt)
+//! after every step. No initial energy guess is needed.
+//!
+//! Each step applies the backward Euler approximation (1 + τ(H - V_min))⁻¹ of
+//! exp(-τ(H - V_min)), which damps every component monotonically in its energy
+//! for any τ. The approximation only affects how fast the state converges, not
+//! the level it converges to.
+
+use crate::error::Error;
+use crate::physics::potentials::Potential;
+use crate::physics::solvers::variational::energy_of;
+use crate::physics::solvers::observer::{Control, Observer, Progress, Silent};
+use crate::physics::solvers::solution::{Convergence, Solution};
+use crate::physics::solvers::{Eigenpair, Solver};
+use crate::utils::integration::Quadrature;
+use crate::utils::tridiagonal::solve_tridiagonal;
+
+#[derive(Clone)]
+pub struct ImaginaryTimeConfig<P> {
+    pub x_min: f64,
+    pub x_max: f64,
+    pub step_size: f64,
+    pub potential: P,
+    /// The imaginary time step τ. Larger steps converge in fewer iterations.
+    pub time_step: f64,
+    pub num_states: usize,
+    /// A level is converged once a step changes its energy by less than this.
+    pub tolerance: f64,
+    /// The maximum number of time steps per level before giving up.
+    pub max_iterations: usize,
+    /// The rule used for the integrals of the energy, the normalization and the
+    /// overlaps between levels.
+    pub quadrature: Quadrature,
+}
+
+/// A solver that finds the lowest `num_states` levels by imaginary-time
+/// propagation. The wavefunction is taken to vanish at `x_min` and `x_max`.
+pub struct ImaginaryTimeSolver<P> {
+    pub config: ImaginaryTimeConfig<P>,
+    steps: usize,
+    energies: Vec<f64>,
+    wavefunctions: Vec<Vec<f64>>,
+}
+
+impl<P: Potential + Clone> ImaginaryTimeSolver<P> {
+    /// Returns the energies of the solved states in increasing order.
+    pub fn energies(&self) -> &[f64] {
+        &self.energies
+    }
+
+    /// Returns a vector of (x, ψ) points for the `n`-th solved state, where `n = 0`
+    /// is the ground state.
+    pub fn wavefunction_points_of(&self, n: usize) -> Vec<(f64, f64)> {
+        self.wavefunctions
+            .get(n)
+            .map(|wavefunction| {
+                wavefunction
+                    .iter()
+                    .enumerate()
+                    .map(|(i, psi)| (self.x_from_index(i), *psi))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn x_from_index(&self, i: usize) -> f64 {
+        self.config.x_min + (i as f64) * self.config.step_size
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        let config = &self.config;
+        if config.step_size <= 0.0 || config.time_step <= 0.0 {
+            return Err(Error::InvalidConfig(
+                "step_size and time_step must be positive".to_string(),
+            ));
+        }
+        if config.x_min >= config.x_max {
+            return Err(Error::InvalidConfig(
+                "x_min must be less than x_max".to_string(),
+            ));
+        }
+        if self.steps < 3 {
+            return Err(Error::StepSizeTooLarge {
+                step_size: config.step_size,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns ∫φψdx.
+    fn overlap(&self, phi: &[f64], psi: &[f64]) -> f64 {
+        let product: Vec<f64> = phi.iter().zip(psi.iter()).map(|(a, b)| a * b).collect();
+        self.config.quadrature.integrate(&product, self.config.step_size)
+    }
+
+    /// Removes the components along the levels found so far and normalizes so that
+    /// ∫|ψ|²dx = 1.
+    fn orthonormalize(&self, psi: &mut [f64]) {
+        for phi in self.wavefunctions.iter() {
+            let overlap = self.overlap(phi, psi);
+            for (val, phi_val) in psi.iter_mut().zip(phi.iter()) {
+                *val -= overlap * phi_val;
+            }
+        }
+
+        let norm = self.overlap(psi, psi).sqrt();
+        for val in psi.iter_mut() {
+            *val /= norm;
+        }
+    }
+
+    /// Propagates until each of the lowest `num_states` levels has converged, one
+    /// level at a time, reporting the energy after every step. Returns the total
+    /// number of steps taken.
+    fn propagate(
+        &mut self,
+        num_states: usize,
+        observer: &mut dyn Observer,
+    ) -> Result<usize, Error> {
+        self.energies.clear();
+        self.wavefunctions.clear();
+        self.validate()?;
+
+        let step_size = self.config.step_size;
+        let time_step = self.config.time_step;
+        let kinetic = 0.5 / (step_size * step_size);
+        let lower_bound = self.energy_lower_bound();
+
+        // (1 + τ(H - V_min)) on the interior points, which is diagonally dominant
+        let interior = self.steps - 2;
+        let lower = vec![-time_step * kinetic; interior];
+        let upper = lower.clone();
+        let diagonal: Vec<f64> = (1..=interior)
+            .map(|i| {
+                let potential = self.config.potential.value(self.x_from_index(i));
+                1.0 + time_step * (2.0 * kinetic + potential - lower_bound)
+            })
+            .collect();
+
+        let mut iteration = 0;
+        for _ in 0..num_states {
+            // A lopsided start overlaps with both even and odd levels
+            let mut psi: Vec<f64> = (0..self.steps)
+                .map(|i| {
+                    if i == 0 || i == self.steps - 1 {
+                        0.0
+                    } else {
+                        1.0 + (i as f64) / (self.steps as f64)
+                    }
+                })
+                .collect();
+            self.orthonormalize(&mut psi);
+
+            let mut energy = f64::INFINITY;
+            let mut converged = false;
+            for _ in 0..self.config.max_iterations {
+                let next = solve_tridiagonal(&lower, &diagonal, &upper, &psi[1..self.steps - 1]);
+                psi[1..self.steps - 1].copy_from_slice(&next);
+                self.orthonormalize(&mut psi);
+
+                let last_energy = energy;
+                energy = energy_of(
+                    &psi,
+                    self.steps,
+                    step_size,
+                    &self.config.potential,
+                    self.config.x_min,
+                    self.config.quadrature,
+                );
+                if !energy.is_finite() {
+                    return Err(Error::NumericalOverflow);
+                }
+                if observer.observe(&Progress::new(iteration, energy)) == Control::Stop {
+                    return Err(Error::Cancelled {
+                        iterations: iteration + 1,
+                    });
+                }
+                iteration += 1;
+                if (last_energy - energy).abs() < self.config.tolerance {
+                    converged = true;
+                    break;
+                }
+            }
+
+            if !converged {
+                return Err(Error::NonConvergence {
+                    iterations: self.config.max_iterations,
+                });
+            }
+            self.energies.push(energy);
+            self.wavefunctions.push(psi);
+        }
+
+        Ok(iteration)
+    }
+}
+
+impl<P: Potential + Clone> Solver for ImaginaryTimeSolver<P> {
+    type CONFIG = ImaginaryTimeConfig<P>;
+
+    fn new(config: &Self::CONFIG) -> Self {
+        let steps = ((config.x_max - config.x_min) / config.step_size).round() as usize + 1;
+
+        ImaginaryTimeSolver {
+            config: config.clone(),
+            steps,
+            energies: Vec::with_capacity(config.num_states),
+            wavefunctions: Vec::with_capacity(config.num_states),
+        }
+    }
+
+    /// Returns the ground state. The other levels are available from
+    /// `energies` and `wavefunction_points_of`.
+    fn solve(&mut self) -> Result<Solution<Self::CONFIG>, Error> {
+        self.solve_observed(&mut Silent)
+    }
+
+    /// Reports the energy of the level being propagated after every step. The
+    /// iterations are counted across all levels.
+    fn solve_observed(
+        &mut self,
+        observer: &mut dyn Observer,
+    ) -> Result<Solution<Self::CONFIG>, Error> {
+        let iterations = self.propagate(self.config.num_states, observer)?;
+        Ok(Solution {
+            quantum_number: Some(0),
+            convergence: Convergence {
+                iterations,
+                energy_error: None,
+            },
+            quadrature: self.config.quadrature,
+            ..Solution::new(&self.wavefunction_points(), self.energy(), &self.config)
+        })
+    }
+
+    /// Returns the ground state energy, or NaN if the solver has not been run.
+    fn energy(&self) -> f64 {
+        self.energies.first().copied().unwrap_or(f64::NAN)
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(&self.config);
+    }
+
+    /// Returns a vector of (x, ψ) points for the ground state wavefunction.
+    fn wavefunction_points(&self) -> Vec<(f64, f64)> {
+        self.wavefunction_points_of(0)
+    }
+
+    fn energy_lower_bound(&self) -> f64 {
+        (0..self.steps)
+            .map(|i| self.config.potential.value(self.x_from_index(i)))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Propagates once for each of the `n` levels. Each level is orthogonal to
+    /// the ones below it, so the `n`-th level has `n` nodes.
+    fn solve_states(&mut self, n: usize) -> Result<Vec<Eigenpair>, Error> {
+        self.propagate(n, &mut Silent)?;
+
+        Ok((0..self.energies.len())
+            .map(|i| Eigenpair {
+                energy: self.energies[i],
+                nodes: i,
+                wavefunction: self.wavefunction_points_of(i),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::physics::harmonic_potential;
+    use crate::physics::solvers::diagonalization::{DiagonalizationConfig, DiagonalizationSolver};
+    use crate::physics::solvers::imaginary_time::*;
+
+    #[test]
+    fn harmonic_oscillator_levels() {
+        let config = ImaginaryTimeConfig {
+            x_min: -6.0,
+            x_max: 6.0,
+            step_size: 0.02,
+            potential: harmonic_potential,
+            time_step: 1.0,
+            num_states: 4,
+            tolerance: 1e-13,
+            max_iterations: 10000,
+            quadrature: Quadrature::Trapezoidal,
+        };
+        let mut solver = ImaginaryTimeSolver::new(&config);
+        let states = solver.solve_states(4).unwrap();
+
+        assert_eq!(states.len(), 4);
+        for (n, state) in states.iter().enumerate() {
+            assert!((state.energy - (n as f64 + 0.5)).abs() < 1e-3);
+        }
+
+        // The same grid eigenvalues as direct diagonalization
+        let mut diagonalization = DiagonalizationSolver::new(&DiagonalizationConfig {
+            x_min: -6.0,
+            x_max: 6.0,
+            step_size: 0.02,
+            potential: harmonic_potential,
+            num_states: 4,
+        });
+        diagonalization.solve().unwrap();
+        for (state, energy) in states.iter().zip(diagonalization.energies()) {
+            assert!((state.energy - energy).abs() < 1e-6);
+        }
+
+        // The levels are orthonormal
+        for (n, phi) in states.iter().enumerate() {
+            for (m, psi) in states.iter().enumerate() {
+                let product: Vec<f64> = phi
+                    .wavefunction
+                    .iter()
+                    .zip(psi.wavefunction.iter())
+                    .map(|((_, a), (_, b))| a * b)
+                    .collect();
+                let expected = if n == m { 1.0 } else { 0.0 };
+                let overlap = config.quadrature.integrate(&product, config.step_size);
+                assert!((overlap - expected).abs() < 1e-6);
+            }
+        }
+
+        assert!(matches!(
+            ImaginaryTimeSolver::new(&ImaginaryTimeConfig {
+                max_iterations: 3,
+                ..config
+            })
+            .solve(),
+            Err(Error::NonConvergence { iterations: 3 })
+        ));
+    }
+}
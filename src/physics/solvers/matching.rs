@@ -1,11 +1,16 @@
 //! Matching method for solving the time-independent Schrodinger equation
 //! in one dimension.
 
+use crate::error::Error;
+use crate::physics::potentials::Potential;
+use crate::physics::solvers::observer::{Control, Observer, Progress, Silent};
+use crate::physics::solvers::solution::{Convergence, Solution};
 use crate::physics::solvers::Solver;
-use crate::utils::integration::trapezoidal;
+use crate::utils::finite_difference::{numerov_step, second_difference_step};
+use crate::utils::integration::Quadrature;
 
 #[derive(Clone)]
-pub struct MatchingConfig {
+pub struct MatchingConfig<P> {
     pub x_min: f64,
     pub x_max: f64,
     pub x_match: f64,
@@ -13,14 +18,21 @@ pub struct MatchingConfig {
     pub initial_energy: f64,
     pub initial_energy_step_size: f64,
     pub energy_step_size_cutoff: f64,
-    pub potential: fn(f64) -> f64,
+    /// The maximum number of candidate energies tried before giving up.
+    pub max_iterations: usize,
+    pub potential: P,
     pub using_numerov: bool,
     pub guarding_scale_factor: bool,
+    /// If set, the solver looks for the state with this many nodes, bracketing its
+    /// energy by counting nodes instead of walking from `initial_energy`.
+    pub quantum_number: Option<usize>,
+    /// The rule used to normalize the wavefunction.
+    pub quadrature: Quadrature,
 }
 
 /// A solver that looks for solutions using the matching method.
-pub struct MatchingSolver {
-    pub config: MatchingConfig,
+pub struct MatchingSolver<P> {
+    pub config: MatchingConfig<P>,
     steps: usize,
     energy: f64,
     energy_step_size: f64,
@@ -29,7 +41,7 @@ pub struct MatchingSolver {
     right_wavefunction: Vec<f64>,
 }
 
-impl MatchingSolver {
+impl<P: Potential + Clone> MatchingSolver<P> {
     fn match_idx(&self) -> usize {
         ((self.config.x_match - self.config.x_min) / self.config.step_size).round() as usize
     }
@@ -45,40 +57,29 @@ impl MatchingSolver {
 
     /// Computes a term needed for the Numerov method.
     fn k_sqr(&self, x: f64) -> f64 {
-        2.0 * (self.energy - (self.config.potential)(x))
+        2.0 * (self.energy - self.config.potential.value(x))
     }
 
     /// Applies the finite difference approximation to find the value of wavefunction
     /// one position toward the matching point from either the left or right.
     fn next(&self, side: &Side, last_index: usize, psi_last: f64, psi_second_to_last: f64) -> f64 {
-        let next: f64;
         if self.config.using_numerov {
-            next = (2.0
-                * (1.0
-                    - (5.0 / 12.0)
-                        * self.config.step_size.powf(2.0)
-                        * self.k_sqr(self.x_from_index(last_index, &side)))
-                * psi_last
-                - (1.0
-                    + (1.0 / 12.0)
-                        * self.config.step_size.powf(2.0)
-                        * self.k_sqr(self.x_from_index(last_index - 1, &side)))
-                    * psi_second_to_last)
-                / (1.0
-                    + (1.0 / 12.0)
-                        * self.config.step_size.powf(2.0)
-                        * self.k_sqr(self.x_from_index(last_index + 1, &side)));
+            numerov_step(
+                psi_last,
+                psi_second_to_last,
+                self.k_sqr(self.x_from_index(last_index + 1, side)),
+                self.k_sqr(self.x_from_index(last_index, side)),
+                self.k_sqr(self.x_from_index(last_index - 1, side)),
+                self.config.step_size,
+            )
         } else {
-            next = 2.0
-                * (self.config.step_size
-                    * self.config.step_size
-                    * ((self.config.potential)(self.x_from_index(last_index, &side))
-                        - self.energy)
-                    + 1.0)
-                * psi_last
-                - psi_second_to_last;
+            second_difference_step(
+                psi_last,
+                psi_second_to_last,
+                self.k_sqr(self.x_from_index(last_index, side)),
+                self.config.step_size,
+            )
         }
-        next
     }
 
     /// Updates the corresponding wavefunction according to the finite difference approximation
@@ -108,7 +109,7 @@ impl MatchingSolver {
     /// Approximates the wavefunction for the current energy. Stops when it has
     /// computed the requested number of steps, or if the wavefunction begins
     /// diverging.
-    fn compute_wavefunction(&mut self) -> Result<(), ()> {
+    fn compute_wavefunction(&mut self) -> Result<(), Error> {
         self.reset_wavefunction();
         for _ in 1..=(self.match_idx() - 1) {
             self.step(&Side::Left);
@@ -122,10 +123,12 @@ impl MatchingSolver {
         let scale_factor =
             self.left_wavefunction.last().unwrap() / self.right_wavefunction.last().unwrap();
 
-        if self.config.guarding_scale_factor
+        if !scale_factor.is_finite() || scale_factor == 0.0 {
+            Err(Error::NumericalOverflow)
+        } else if self.config.guarding_scale_factor
             && (scale_factor.abs() > 100.0 || scale_factor.abs().recip() > 100.0)
         {
-            Err(())
+            Err(Error::ScaleFactorOutOfRange { scale_factor })
         } else {
             self.right_wavefunction
                 .iter_mut()
@@ -205,11 +208,76 @@ impl MatchingSolver {
         (left_slope, right_slope)
     }
 
+    /// Searches for the level by adjusting the energy until the slopes match. If
+    /// `level` holds the number of nodes and an energy interval containing the
+    /// level, the search starts from the middle of the interval instead of
+    /// `initial_energy`, and the width of the interval is the energy error.
+    fn search(
+        &mut self,
+        observer: &mut dyn Observer,
+        level: Option<(usize, (f64, f64))>,
+    ) -> Result<Solution<MatchingConfig<P>>, Error> {
+        if let Some((_, (lower, upper))) = level {
+            self.set_initial_energy(0.5 * (lower + upper), 0.0);
+        }
+
+        for iteration in 0..self.config.max_iterations {
+            let result = self.compute_wavefunction();
+            let (_, f): (Vec<_>, Vec<_>) = self.wavefunction_points().iter().cloned().unzip();
+            //let e = energy_of(&f, self.steps, self.step_size, self.potential, self.x_min);
+            //dbg!(self.energy, e);
+
+            let (left_slope, right_slope) = self.slopes();
+            let progress = Progress {
+                step_size: Some(self.energy_step_size),
+                slopes: Some((left_slope, right_slope)),
+                ..Progress::new(iteration, self.energy)
+            };
+            if observer.observe(&progress) == Control::Stop {
+                return Err(Error::Cancelled {
+                    iterations: iteration + 1,
+                });
+            }
+
+            if self.energy_step_size.abs() <= self.config.energy_step_size_cutoff {
+                result?;
+                self.normalize();
+                return Ok(Solution {
+                    quantum_number: level.map(|(n, _)| n),
+                    convergence: Convergence {
+                        iterations: iteration + 1,
+                        energy_error: Some(match level {
+                            Some((_, (lower, upper))) => upper - lower,
+                            None => self.energy_step_size.abs(),
+                        }),
+                    },
+                    quadrature: self.config.quadrature,
+                    ..Solution::new(&self.wavefunction_points(), self.energy, &self.config)
+                });
+            }
+
+            if result.is_ok() && self.is_left_slope_larger == Some(left_slope < right_slope) {
+                self.energy_step_size = -self.energy_step_size / 2.0;
+            }
+
+            self.is_left_slope_larger = Some(left_slope >= right_slope);
+
+            self.energy += self.energy_step_size;
+            if !self.energy.is_finite() {
+                return Err(Error::NumericalOverflow);
+            }
+        }
+
+        Err(Error::NonConvergence {
+            iterations: self.config.max_iterations,
+        })
+    }
+
     /// Normalizes the wavefunction so that ∫|ψ|²dx = 1.
     fn normalize(&mut self) {
         let (_, mut f): (Vec<_>, Vec<_>) = self.wavefunction_points().iter().cloned().unzip();
         f = f.iter().map(|val| val * val).collect();
-        let integral = trapezoidal(&f, &self.config.step_size);
+        let integral = self.config.quadrature.integrate(&f, self.config.step_size);
         self.left_wavefunction
             .iter_mut()
             .for_each(|val| *val = *val * (1.0 / integral).sqrt());
@@ -220,8 +288,8 @@ impl MatchingSolver {
     }
 }
 
-impl Solver for MatchingSolver {
-    type CONFIG = MatchingConfig;
+impl<P: Potential + Clone> Solver for MatchingSolver<P> {
+    type CONFIG = MatchingConfig<P>;
 
     fn new(config: &Self::CONFIG) -> Self {
         let steps = ((config.x_max - config.x_min) / config.step_size).round() as usize + 1;
@@ -234,38 +302,46 @@ impl Solver for MatchingSolver {
             energy_step_size: config.initial_energy_step_size,
             is_left_slope_larger: None,
             left_wavefunction: Vec::<f64>::with_capacity(match_idx + 1),
-            right_wavefunction: Vec::<f64>::with_capacity(steps - match_idx),
+            right_wavefunction: Vec::<f64>::with_capacity(steps.saturating_sub(match_idx)),
         }
     }
 
     /// Popuplates the wavefunction vector with a solution to the Schrodinger equation
     /// and also determines the corresponding energy. The process requires iterating
     /// over many candidate energies and stopping when the energy step size becomes
-    /// sufficiently small.
-    fn solve(&mut self) {
-        loop {
-            let result = self.compute_wavefunction();
-            let (_, f): (Vec<_>, Vec<_>) = self.wavefunction_points().iter().cloned().unzip();
-            //let e = energy_of(&f, self.steps, self.step_size, self.potential, self.x_min);
-            //dbg!(self.energy, e);
-
-            if self.energy_step_size.abs() <= self.config.energy_step_size_cutoff {
-                self.normalize();
-                break;
-            }
-
-            let (left_slope, right_slope) = self.slopes();
+    /// sufficiently small. If a quantum number is requested, the energy is first
+    /// bracketed using the node theorem, so that the requested state is guaranteed
+    /// to be found.
+    fn solve(&mut self) -> Result<Solution<Self::CONFIG>, Error> {
+        self.solve_observed(&mut Silent)
+    }
 
-            if result.is_ok() && self.is_left_slope_larger == Some(left_slope < right_slope) {
-                self.energy_step_size = -self.energy_step_size / 2.0;
+    /// Reports the energy, energy step size and slopes at the matching point for
+    /// each candidate energy.
+    fn solve_observed(
+        &mut self,
+        observer: &mut dyn Observer,
+    ) -> Result<Solution<Self::CONFIG>, Error> {
+        self.validate()?;
+
+        match self.config.quantum_number {
+            Some(n) => {
+                let bracket = self.bracket_level(n)?;
+                self.search(observer, Some((n, bracket)))
             }
-
-            self.is_left_slope_larger = Some(left_slope >= right_slope);
-
-            self.energy += self.energy_step_size;
+            None => self.search(observer, None),
         }
     }
 
+    fn solve_from_bracket(
+        &mut self,
+        nodes: usize,
+        bracket: (f64, f64),
+    ) -> Result<Solution<Self::CONFIG>, Error> {
+        self.validate()?;
+        self.search(&mut Silent, Some((nodes, bracket)))
+    }
+
     fn energy(&self) -> f64 {
         self.energy
     }
@@ -287,6 +363,65 @@ impl Solver for MatchingSolver {
 
         pairs
     }
+
+    /// Checks that the configuration describes a usable grid.
+    fn validate(&self) -> Result<(), Error> {
+        let config = &self.config;
+        if config.step_size <= 0.0 {
+            return Err(Error::InvalidConfig("step_size must be positive".to_string()));
+        }
+        if config.x_min >= config.x_max {
+            return Err(Error::InvalidConfig("x_min must be less than x_max".to_string()));
+        }
+        if !(config.x_min..=config.x_max).contains(&config.x_match) {
+            return Err(Error::InvalidConfig(
+                "x_match must lie within [x_min, x_max]".to_string(),
+            ));
+        }
+        if self.match_idx() < 2 || self.steps < self.match_idx() + 3 {
+            return Err(Error::StepSizeTooLarge {
+                step_size: config.step_size,
+            });
+        }
+        Ok(())
+    }
+
+    fn energy_lower_bound(&self) -> f64 {
+        (0..self.steps)
+            .map(|i| {
+                self.config
+                    .potential
+                    .value(self.x_from_index(i, &Side::Left))
+            })
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Integrates the left wavefunction across the whole grid at the given energy.
+    /// The integration stops early if the wavefunction overflows. This overwrites
+    /// the current wavefunction.
+    fn trial_wavefunction(&mut self, energy: f64) -> Option<Vec<f64>> {
+        self.energy = energy;
+        self.reset_wavefunction();
+        for _ in 1..=(self.steps - 2) {
+            self.step(&Side::Left);
+            if !self.left_wavefunction.last().unwrap().is_finite() {
+                self.left_wavefunction.pop();
+                break;
+            }
+        }
+
+        Some(self.left_wavefunction.clone())
+    }
+
+    fn set_initial_energy(&mut self, energy: f64, energy_step_size: f64) {
+        self.energy = energy;
+        self.energy_step_size = energy_step_size;
+        self.is_left_slope_larger = None;
+    }
+
+    fn energy_tolerance(&self) -> f64 {
+        self.config.energy_step_size_cutoff
+    }
 }
 
 /// Indicates the side of a component wavefunction in the matching method.
@@ -294,3 +429,128 @@ enum Side {
     Left,
     Right,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::physics::harmonic_potential;
+    use crate::physics::solvers::matching::*;
+
+    /// The harmonic oscillator on [-6, 6], which the tests vary with `..config`.
+    fn harmonic_config() -> MatchingConfig<fn(f64) -> f64> {
+        MatchingConfig {
+            x_min: -6.0,
+            x_max: 6.0,
+            x_match: -1.0,
+            step_size: 0.01,
+            initial_energy: 0.0,
+            initial_energy_step_size: 0.1,
+            energy_step_size_cutoff: 1e-8,
+            max_iterations: 10000,
+            potential: harmonic_potential,
+            using_numerov: true,
+            guarding_scale_factor: false,
+            quantum_number: None,
+            quadrature: Quadrature::Trapezoidal,
+        }
+    }
+
+    #[test]
+    fn spectrum() {
+        let mut solver = MatchingSolver::new(&harmonic_config());
+        let states = solver.solve_states(5).unwrap();
+
+        assert_eq!(states.len(), 5);
+        for (n, state) in states.iter().enumerate() {
+            assert_eq!(state.nodes, n);
+            assert!((state.energy - (n as f64 + 0.5)).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn requested_quantum_number() {
+        let config = MatchingConfig {
+            x_match: 0.7,
+            quantum_number: Some(3),
+            ..harmonic_config()
+        };
+        let mut solver = MatchingSolver::new(&config);
+        let solution = solver.solve().unwrap();
+        assert!((solution.energy - 3.5).abs() < 1e-4);
+
+        // The error is the width of the bracket, not the zero energy step size
+        let error = solution.convergence.energy_error.unwrap();
+        assert!(error > 0.0 && error <= config.energy_step_size_cutoff);
+
+        // A requested quantum number does not restrict the spectrum
+        let states = solver.solve_states(4).unwrap();
+        assert_eq!(states.len(), 4);
+        for (n, state) in states.iter().enumerate() {
+            assert_eq!(state.nodes, n);
+            assert!((state.energy - (n as f64 + 0.5)).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn solver_errors() {
+        let config = MatchingConfig {
+            x_min: -5.0,
+            x_max: 5.0,
+            x_match: 6.0,
+            step_size: 0.1,
+            initial_energy: 1.45,
+            energy_step_size_cutoff: 1e-5,
+            max_iterations: 10,
+            guarding_scale_factor: true,
+            ..harmonic_config()
+        };
+        let mut solver = MatchingSolver::new(&config);
+        assert!(matches!(solver.solve(), Err(Error::InvalidConfig(_))));
+        assert!(matches!(solver.solve_states(2), Err(Error::InvalidConfig(_))));
+
+        let mut solver = MatchingSolver::new(&MatchingConfig {
+            x_min: 5.0,
+            x_max: -5.0,
+            x_match: 0.0,
+            ..config
+        });
+        assert!(matches!(solver.solve_states(2), Err(Error::InvalidConfig(_))));
+
+        let mut solver = MatchingSolver::new(&MatchingConfig {
+            x_match: -1.0,
+            ..config
+        });
+        assert!(matches!(
+            solver.solve(),
+            Err(Error::NonConvergence { iterations: 10 })
+        ));
+    }
+
+    #[test]
+    fn observed_progress() {
+        let config = harmonic_config();
+        let mut solver = MatchingSolver::new(&config);
+
+        let mut history = Vec::new();
+        solver
+            .solve_observed(&mut |progress: &Progress| {
+                history.push(*progress);
+                Control::Continue
+            })
+            .unwrap();
+        let last = history.last().unwrap();
+        assert_eq!(last.iteration + 1, history.len());
+        assert_eq!(last.energy, solver.energy());
+        assert!(last.step_size.unwrap().abs() <= config.energy_step_size_cutoff);
+        assert!(history.iter().all(|progress| progress.slopes.is_some()));
+
+        solver.reset();
+        let result = solver.solve_observed(&mut |progress: &Progress| {
+            if progress.iteration == 4 {
+                Control::Stop
+            } else {
+                Control::Continue
+            }
+        });
+        assert!(matches!(result, Err(Error::Cancelled { iterations: 5 })));
+    }
+}
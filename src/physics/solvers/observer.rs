@@ -0,0 +1,72 @@
+//! Hooks for following the progress of a solver. Solvers never print on their
+//! own; instead `Solver::solve_observed` reports each iteration to an `Observer`,
+//! which may log it, record it for a convergence plot or stop the solver early.
+//! The propagators in `physics::dynamics` report each time step the same way.
+
+/// The state of a solver after one iteration.
+#[derive(Clone, Copy, Debug)]
+pub struct Progress {
+    /// The iteration number, counting from zero.
+    pub iteration: usize,
+    /// The current energy estimate.
+    pub energy: f64,
+    /// The current step size: the energy step of the matching and shooting
+    /// methods, or the largest change in ψ of the variational method.
+    pub step_size: Option<f64>,
+    /// The (left, right) slopes at the matching point of the matching method.
+    pub slopes: Option<(f64, f64)>,
+    /// Whether the change tried by the variational method, or the move proposed
+    /// by variational Monte Carlo, was accepted.
+    pub accepted: Option<bool>,
+    /// The number of walkers of diffusion Monte Carlo.
+    pub walkers: Option<usize>,
+    /// The reference energy of diffusion Monte Carlo.
+    pub reference_energy: Option<f64>,
+    /// The time reached by a propagator.
+    pub time: Option<f64>,
+}
+
+impl Progress {
+    /// Returns the progress at `iteration` with only the energy known.
+    pub fn new(iteration: usize, energy: f64) -> Self {
+        Progress {
+            iteration,
+            energy,
+            step_size: None,
+            slopes: None,
+            accepted: None,
+            walkers: None,
+            reference_energy: None,
+            time: None,
+        }
+    }
+}
+
+/// What a solver should do after reporting an iteration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Control {
+    Continue,
+    /// Stop the solver, which then returns `Error::Cancelled`.
+    Stop,
+}
+
+pub trait Observer {
+    fn observe(&mut self, progress: &Progress) -> Control;
+}
+
+/// An observer that ignores every iteration. `Solver::solve` uses it.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Silent;
+
+impl Observer for Silent {
+    fn observe(&mut self, _progress: &Progress) -> Control {
+        Control::Continue
+    }
+}
+
+/// Any closure taking the progress can be used as an observer.
+impl<F: FnMut(&Progress) -> Control> Observer for F {
+    fn observe(&mut self, progress: &Progress) -> Control {
+        self(progress)
+    }
+}
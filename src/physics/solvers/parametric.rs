@@ -0,0 +1,309 @@
+//! Parametric variational method for finding the ground state. The trial
+//! wavefunction ψ(x; α, β, …) belongs to a family chosen by the user, and its
+//! energy is minimized over the parameters. The minimum is an upper bound on the
+//! ground state energy.
+
+use crate::error::Error;
+use crate::physics::potentials::Potential;
+use crate::physics::solvers::variational::energy_of;
+use crate::physics::solvers::observer::{Control, Observer, Progress, Silent};
+use crate::physics::solvers::solution::{Convergence, Solution};
+use crate::physics::solvers::{Eigenpair, Solver};
+use crate::utils::integration::Quadrature;
+use crate::utils::optimization::{gradient_descent, nelder_mead, Minimum};
+
+/// The method used to minimize the energy over the parameters.
+#[derive(Clone, Copy, Debug)]
+pub enum Optimizer {
+    /// The derivative-free Nelder-Mead simplex method, starting from a simplex
+    /// with edges of length `initial_step`.
+    NelderMead { initial_step: f64 },
+    /// Gradient descent with central difference gradients.
+    GradientDescent { learning_rate: f64 },
+}
+
+#[derive(Clone)]
+pub struct ParametricConfig<P, F> {
+    pub x_min: f64,
+    pub x_max: f64,
+    pub step_size: f64,
+    pub potential: P,
+    /// The trial family, taking x and the parameters and returning ψ(x). It does
+    /// not need to be normalized.
+    pub trial_wavefunction: F,
+    pub initial_parameters: Vec<f64>,
+    pub optimizer: Optimizer,
+    /// The optimization stops once the energy changes by less than this.
+    pub tolerance: f64,
+    /// The maximum number of optimizer iterations before giving up.
+    pub max_iterations: usize,
+    /// The rule used for the integrals of the energy and the normalization.
+    pub quadrature: Quadrature,
+}
+
+/// A solver that minimizes the energy of a parametrized trial wavefunction.
+pub struct ParametricSolver<P, F> {
+    pub config: ParametricConfig<P, F>,
+    steps: usize,
+    parameters: Vec<f64>,
+    energy: f64,
+    history: Vec<f64>,
+}
+
+impl<P, F> ParametricSolver<P, F>
+where
+    P: Potential + Clone,
+    F: Fn(f64, &[f64]) -> f64 + Clone,
+{
+    /// Returns the parameters of the current trial wavefunction, which are optimal
+    /// after a successful `solve`.
+    pub fn parameters(&self) -> &[f64] {
+        &self.parameters
+    }
+
+    /// Returns the lowest energy found after each optimizer iteration.
+    pub fn history(&self) -> &[f64] {
+        &self.history
+    }
+
+    fn x_from_index(&self, i: usize) -> f64 {
+        self.config.x_min + (i as f64) * self.config.step_size
+    }
+
+    /// Samples the trial wavefunction for the given parameters on the grid.
+    fn sample(&self, parameters: &[f64]) -> Vec<f64> {
+        (0..self.steps)
+            .map(|i| (self.config.trial_wavefunction)(self.x_from_index(i), parameters))
+            .collect()
+    }
+
+    /// Returns the energy of the trial wavefunction for the given parameters.
+    fn energy_at(&self, parameters: &[f64]) -> f64 {
+        energy_of(
+            &self.sample(parameters),
+            self.steps,
+            self.config.step_size,
+            &self.config.potential,
+            self.config.x_min,
+            self.config.quadrature,
+        )
+    }
+}
+
+impl<P, F> Solver for ParametricSolver<P, F>
+where
+    P: Potential + Clone,
+    F: Fn(f64, &[f64]) -> f64 + Clone,
+{
+    type CONFIG = ParametricConfig<P, F>;
+
+    fn new(config: &Self::CONFIG) -> Self {
+        let steps = ((config.x_max - config.x_min) / config.step_size).round() as usize + 1;
+        ParametricSolver {
+            config: config.clone(),
+            steps,
+            parameters: config.initial_parameters.clone(),
+            energy: f64::NAN,
+            history: Vec::new(),
+        }
+    }
+
+    /// Minimizes the energy over the parameters with the configured optimizer.
+    fn solve(&mut self) -> Result<Solution<Self::CONFIG>, Error> {
+        self.solve_observed(&mut Silent)
+    }
+
+    /// Reports the lowest energy found after every optimizer iteration.
+    fn solve_observed(
+        &mut self,
+        observer: &mut dyn Observer,
+    ) -> Result<Solution<Self::CONFIG>, Error> {
+        if self.config.step_size <= 0.0 {
+            return Err(Error::InvalidConfig(
+                "step_size must be positive".to_string(),
+            ));
+        }
+        if self.config.x_min >= self.config.x_max {
+            return Err(Error::InvalidConfig(
+                "x_min must be less than x_max".to_string(),
+            ));
+        }
+        if self.config.initial_parameters.is_empty() {
+            return Err(Error::InvalidConfig(
+                "initial_parameters must not be empty".to_string(),
+            ));
+        }
+        if self.steps < 3 {
+            return Err(Error::StepSizeTooLarge {
+                step_size: self.config.step_size,
+            });
+        }
+
+        let objective = |parameters: &[f64]| self.energy_at(parameters);
+        let observe = |iteration, energy| {
+            observer.observe(&Progress::new(iteration, energy)) == Control::Continue
+        };
+        let Minimum {
+            point,
+            value,
+            history,
+        } = match self.config.optimizer {
+            Optimizer::NelderMead { initial_step } => nelder_mead(
+                objective,
+                &self.config.initial_parameters,
+                initial_step,
+                self.config.tolerance,
+                self.config.max_iterations,
+                observe,
+            )?,
+            Optimizer::GradientDescent { learning_rate } => gradient_descent(
+                objective,
+                &self.config.initial_parameters,
+                learning_rate,
+                self.config.tolerance,
+                self.config.max_iterations,
+                observe,
+            )?,
+        };
+
+        self.parameters = point;
+        self.energy = value;
+        self.history = history;
+        Ok(Solution {
+            quantum_number: Some(0),
+            convergence: Convergence {
+                iterations: self.history.len(),
+                energy_error: None,
+            },
+            quadrature: self.config.quadrature,
+            ..Solution::new(&self.wavefunction_points(), self.energy, &self.config)
+        })
+    }
+
+    fn energy(&self) -> f64 {
+        self.energy
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(&self.config);
+    }
+
+    /// Returns the trial wavefunction for the current parameters, normalized so that
+    /// ∫|ψ|²dx = 1.
+    fn wavefunction_points(&self) -> Vec<(f64, f64)> {
+        let psi = self.sample(&self.parameters);
+        let density: Vec<f64> = psi.iter().map(|val| val * val).collect();
+        let norm = self.config.quadrature.integrate(&density, self.config.step_size).sqrt();
+
+        psi.iter()
+            .enumerate()
+            .map(|(i, val)| (self.x_from_index(i), val / norm))
+            .collect()
+    }
+
+    fn energy_lower_bound(&self) -> f64 {
+        (0..self.steps)
+            .map(|i| self.config.potential.value(self.x_from_index(i)))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// The variational method only converges to the ground state, so at most one
+    /// level is returned.
+    fn solve_states(&mut self, n: usize) -> Result<Vec<Eigenpair>, Error> {
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+
+        self.solve()?;
+        Ok(vec![Eigenpair {
+            energy: self.energy(),
+            nodes: 0,
+            wavefunction: self.wavefunction_points(),
+        }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::physics::harmonic_potential;
+    use crate::physics::solvers::parametric::*;
+
+    #[test]
+    fn gaussian_trial_wavefunction() {
+        let config = ParametricConfig {
+            x_min: -6.0,
+            x_max: 6.0,
+            step_size: 0.01,
+            potential: harmonic_potential,
+            trial_wavefunction: |x: f64, parameters: &[f64]| (-parameters[0] * x * x).exp(),
+            initial_parameters: vec![2.0],
+            optimizer: Optimizer::NelderMead { initial_step: 0.5 },
+            tolerance: 1e-12,
+            max_iterations: 1000,
+            quadrature: Quadrature::Trapezoidal,
+        };
+
+        // The exact ground state is in the family, with α = 1/2
+        let mut solver = ParametricSolver::new(&config);
+        let mut energies = Vec::new();
+        solver
+            .solve_observed(&mut |progress: &Progress| {
+                energies.push(progress.energy);
+                Control::Continue
+            })
+            .unwrap();
+        assert!((solver.parameters()[0] - 0.5).abs() < 1e-3);
+        assert!((solver.energy() - 0.5).abs() < 1e-4);
+        assert!(solver.history().windows(2).all(|w| w[1] <= w[0]));
+        assert_eq!(energies, solver.history());
+
+        let mut solver = ParametricSolver::new(&ParametricConfig {
+            optimizer: Optimizer::GradientDescent { learning_rate: 0.1 },
+            ..config.clone()
+        });
+        solver.solve().unwrap();
+        assert!((solver.parameters()[0] - 0.5).abs() < 1e-3);
+        assert!((solver.energy() - 0.5).abs() < 1e-4);
+
+        let mut solver = ParametricSolver::new(&ParametricConfig {
+            initial_parameters: Vec::new(),
+            tolerance: 0.0,
+            ..config
+        });
+        assert!(matches!(solver.solve(), Err(Error::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn higher_order_quadrature() {
+        // exp(-α|x|) has a kink at x = 0, where the trapezoidal rule is least accurate
+        let config = ParametricConfig {
+            x_min: -6.0,
+            x_max: 6.0,
+            step_size: 0.1,
+            potential: harmonic_potential,
+            trial_wavefunction: |x: f64, parameters: &[f64]| (-parameters[0] * x.abs()).exp(),
+            initial_parameters: vec![1.0],
+            optimizer: Optimizer::NelderMead { initial_step: 0.5 },
+            tolerance: 1e-12,
+            max_iterations: 1000,
+            quadrature: Quadrature::Trapezoidal,
+        };
+
+        // Returns the error of ∫|ψ|²dx = ψ(0)²(1 - exp(-2αx_max))/α for the returned ψ
+        let normalization_error = |quadrature| {
+            let mut solver = ParametricSolver::new(&ParametricConfig {
+                quadrature,
+                ..config.clone()
+            });
+            solver.solve().unwrap();
+            let alpha = solver.parameters()[0];
+            let psi_0 = solver.wavefunction_points()[60].1;
+            (psi_0 * psi_0 * (1.0 - (-2.0 * alpha * config.x_max).exp()) / alpha - 1.0).abs()
+        };
+        let trapezoidal = normalization_error(Quadrature::Trapezoidal);
+        let simpson = normalization_error(Quadrature::Simpson);
+        let boole = normalization_error(Quadrature::Boole);
+        assert!(simpson < 0.1 * trapezoidal);
+        assert!(boole < 0.1 * simpson);
+    }
+}
@@ -0,0 +1,228 @@
+//! Solver for the radial Schrodinger equation of a central potential,
+//! u''(r) = 2(V(r) + l(l + 1)/2r² - E)u(r), where the three-dimensional
+//! wavefunction is u(r)/r times a spherical harmonic. The equation is solved with
+//! the matching method and Numerov's method on 0 <= r <= `r_max`, with u(0) = 0.
+
+use crate::error::Error;
+use crate::physics::potentials::Potential;
+use crate::physics::solvers::matching::{MatchingConfig, MatchingSolver};
+use crate::physics::solvers::observer::{Observer, Silent};
+use crate::physics::solvers::solution::Solution;
+use crate::physics::solvers::Solver;
+use crate::utils::integration::Quadrature;
+
+#[derive(Clone)]
+pub struct RadialConfig<P> {
+    pub r_max: f64,
+    pub r_match: f64,
+    pub step_size: f64,
+    /// The angular momentum quantum number.
+    pub l: usize,
+    pub initial_energy: f64,
+    pub initial_energy_step_size: f64,
+    pub energy_step_size_cutoff: f64,
+    /// The maximum number of candidate energies tried before giving up.
+    pub max_iterations: usize,
+    pub potential: P,
+    pub guarding_scale_factor: bool,
+    /// If set, the solver looks for the state with this many radial nodes
+    /// (excluding r = 0), bracketing its energy by counting nodes.
+    pub quantum_number: Option<usize>,
+    /// The rule used to normalize the wavefunction.
+    pub quadrature: Quadrature,
+}
+
+/// The potential V(r) + l(l + 1)/2r² seen by u(r).
+#[derive(Clone, Copy, Debug)]
+pub struct EffectivePotential<P> {
+    pub potential: P,
+    pub l: usize,
+}
+
+impl<P: Potential> Potential for EffectivePotential<P> {
+    /// At r = 0 both terms may be singular, but Numerov's method only uses the value
+    /// there multiplied by u(0) = 0. Zero is returned so that the product vanishes
+    /// instead of becoming NaN.
+    fn value(&self, r: f64) -> f64 {
+        if r <= 0.0 {
+            return 0.0;
+        }
+        let l = self.l as f64;
+        self.potential.value(r) + 0.5 * l * (l + 1.0) / (r * r)
+    }
+}
+
+/// A solver that looks for solutions of the radial equation. The wavefunction
+/// points are (r, u(r)) with ∫|u|²dr = 1.
+pub struct RadialSolver<P> {
+    pub config: RadialConfig<P>,
+    matching: MatchingSolver<EffectivePotential<P>>,
+}
+
+impl<P: Potential + Clone> RadialSolver<P> {
+    /// Returns the matching configuration for the effective potential on [0, r_max].
+    fn matching_config(config: &RadialConfig<P>) -> MatchingConfig<EffectivePotential<P>> {
+        MatchingConfig {
+            x_min: 0.0,
+            x_max: config.r_max,
+            x_match: config.r_match,
+            step_size: config.step_size,
+            initial_energy: config.initial_energy,
+            initial_energy_step_size: config.initial_energy_step_size,
+            energy_step_size_cutoff: config.energy_step_size_cutoff,
+            max_iterations: config.max_iterations,
+            potential: EffectivePotential {
+                potential: config.potential.clone(),
+                l: config.l,
+            },
+            using_numerov: true,
+            guarding_scale_factor: config.guarding_scale_factor,
+            quantum_number: config.quantum_number,
+            quadrature: config.quadrature,
+        }
+    }
+}
+
+impl<P: Potential + Clone> Solver for RadialSolver<P> {
+    type CONFIG = RadialConfig<P>;
+
+    fn new(config: &Self::CONFIG) -> Self {
+        RadialSolver {
+            config: config.clone(),
+            matching: MatchingSolver::new(&Self::matching_config(config)),
+        }
+    }
+
+    /// Finds a solution with the matching method, integrating outward from
+    /// u(0) = 0 and inward from u(r_max) = 0.
+    fn solve(&mut self) -> Result<Solution<Self::CONFIG>, Error> {
+        self.solve_observed(&mut Silent)
+    }
+
+    fn solve_observed(
+        &mut self,
+        observer: &mut dyn Observer,
+    ) -> Result<Solution<Self::CONFIG>, Error> {
+        self.validate()?;
+        Ok(self
+            .matching
+            .solve_observed(observer)?
+            .with_config(self.config.clone()))
+    }
+
+    fn solve_from_bracket(
+        &mut self,
+        nodes: usize,
+        bracket: (f64, f64),
+    ) -> Result<Solution<Self::CONFIG>, Error> {
+        Ok(self
+            .matching
+            .solve_from_bracket(nodes, bracket)?
+            .with_config(self.config.clone()))
+    }
+
+    fn energy(&self) -> f64 {
+        self.matching.energy()
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(&self.config)
+    }
+
+    fn wavefunction_points(&self) -> Vec<(f64, f64)> {
+        self.matching.wavefunction_points()
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if self.config.r_max <= 0.0 {
+            return Err(Error::InvalidConfig(
+                "r_max must be positive".to_string(),
+            ));
+        }
+        self.matching.validate()
+    }
+
+    fn energy_lower_bound(&self) -> f64 {
+        self.matching.energy_lower_bound()
+    }
+
+    fn trial_wavefunction(&mut self, energy: f64) -> Option<Vec<f64>> {
+        self.matching.trial_wavefunction(energy)
+    }
+
+    fn set_initial_energy(&mut self, energy: f64, energy_step_size: f64) {
+        self.matching.set_initial_energy(energy, energy_step_size)
+    }
+
+    fn energy_tolerance(&self) -> f64 {
+        self.matching.energy_tolerance()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::physics::harmonic_potential;
+    use crate::physics::solvers::radial::*;
+
+    #[test]
+    fn hydrogen_levels() {
+        let config = RadialConfig {
+            r_max: 60.0,
+            r_match: 1.0,
+            step_size: 1e-3,
+            l: 0,
+            initial_energy: -1.0,
+            initial_energy_step_size: 0.1,
+            energy_step_size_cutoff: 1e-10,
+            max_iterations: 10000,
+            potential: |r: f64| -1.0 / r,
+            guarding_scale_factor: false,
+            quantum_number: Some(1),
+            quadrature: Quadrature::Trapezoidal,
+        };
+
+        // E = -1/2n² with n = n_r + l + 1
+        let mut solver = RadialSolver::new(&config);
+        solver.solve().unwrap();
+        assert!((solver.energy() + 0.125).abs() < 1e-5);
+
+        let mut solver = RadialSolver::new(&RadialConfig {
+            l: 1,
+            quantum_number: None,
+            ..config
+        });
+        let states = solver.solve_states(2).unwrap();
+        assert!((states[0].energy + 0.125).abs() < 1e-5);
+        assert!((states[1].energy + 1.0 / 18.0).abs() < 1e-5);
+
+        let mut solver = RadialSolver::new(&RadialConfig { r_max: -1.0, ..config });
+        assert!(matches!(solver.solve_states(2), Err(Error::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn isotropic_oscillator_levels() {
+        for l in 0..3 {
+            let mut solver = RadialSolver::new(&RadialConfig {
+                r_max: 8.0,
+                r_match: 1.5,
+                step_size: 1e-3,
+                l,
+                initial_energy: 0.0,
+                initial_energy_step_size: 0.1,
+                energy_step_size_cutoff: 1e-10,
+                max_iterations: 10000,
+                potential: harmonic_potential,
+                guarding_scale_factor: false,
+                quantum_number: None,
+                quadrature: Quadrature::Trapezoidal,
+            });
+
+            // E = 2n_r + l + 3/2
+            let states = solver.solve_states(3).unwrap();
+            for (n_r, state) in states.iter().enumerate() {
+                assert_eq!(state.nodes, n_r);
+                assert!((state.energy - (2.0 * n_r as f64 + l as f64 + 1.5)).abs() < 1e-5);
+            }
+        }
+    }
+}
@@ -1,45 +1,93 @@
 //! Shooting method for solving the time-independent Schrodinger equation
-//! in one dimension. The shooting method is only applicable for even
-//! potentials (symmetric about x = 0).
+//! in one dimension. By default the wavefunction is integrated from x = 0 and
+//! mirrored according to its parity, which requires an even potential (symmetric
+//! about x = 0). If `x_min` is set, the wavefunction is instead integrated from
+//! ψ(x_min) = 0 across the whole grid, which works for any potential.
 
+use crate::error::Error;
+use crate::physics::potentials::Potential;
+use crate::physics::solvers::observer::{Control, Observer, Progress, Silent};
+use crate::physics::solvers::solution::{Convergence, Solution};
 use crate::physics::solvers::Solver;
+use crate::utils::finite_difference::{numerov_step, second_difference_step};
+use crate::utils::integration::Quadrature;
 
 /// Configuration for the shooting solver
 #[derive(Clone)]
-pub struct ShootingConfig {
+pub struct ShootingConfig<P> {
+    /// If set, the solver integrates from `x_min` to `x_max` instead of from
+    /// x = 0, and `parity` is ignored.
+    pub x_min: Option<f64>,
     pub x_max: f64,
     pub step_size: f64,
     pub initial_energy: f64,
     pub intitial_energy_step_size: f64,
     pub wavefunction_cutoff: f64,
-    pub potential: fn(f64) -> f64,
+    pub potential: P,
     pub energy_step_size_cutoff: f64,
+    /// The maximum number of candidate energies tried before giving up.
+    pub max_iterations: usize,
     pub parity: Parity,
+    /// Whether to step with Numerov's method instead of the second order central
+    /// difference, as in `MatchingConfig`.
+    pub using_numerov: bool,
+    /// If set, the solver looks for the state with this many nodes, bracketing its
+    /// energy by counting nodes instead of walking from `initial_energy`. The
+    /// quantum number must be even for `Parity::Even` and odd for `Parity::Odd`.
+    pub quantum_number: Option<usize>,
+    /// The rule used to normalize the wavefunction.
+    pub quadrature: Quadrature,
 }
 
 /// A solver that looks for solutions of the desired parity
 /// using the shooting method.
-pub struct ShootingSolver {
-    pub config: ShootingConfig,
+pub struct ShootingSolver<P> {
+    pub config: ShootingConfig<P>,
     energy: f64,
     energy_step_size: f64,
     wavefunction: Vec<f64>,
     last_diverge: f64,
 }
 
-impl ShootingSolver {
+impl<P: Potential + Clone> ShootingSolver<P> {
+    /// Returns the number of grid points the wavefunction is integrated over.
+    fn steps(&self) -> usize {
+        let x_start = self.config.x_min.unwrap_or(0.0);
+        ((self.config.x_max - x_start) / self.config.step_size).round() as usize + 1
+    }
+
+    /// Returns the x value associated with an index into the wavefunction vector.
+    fn x_from_index(&self, i: usize) -> f64 {
+        self.config.x_min.unwrap_or(0.0) + (i as f64) * self.config.step_size
+    }
+
+    /// Computes a term needed for the finite difference approximation.
+    fn k_sqr(&self, i: usize) -> f64 {
+        2.0 * (self.energy - self.config.potential.value(self.x_from_index(i)))
+    }
+
     /// Applies the finite difference approximation to find value of wavefunction
     /// one position forward, using the two most recent values.
     fn step(&mut self) {
         let i = self.wavefunction.len() - 1;
-        self.wavefunction.push(
-            2.0 * self.wavefunction[i]
-                - self.wavefunction[i - 1]
-                - 2.0
-                    * (self.energy - (self.config.potential)((i as f64) * self.config.step_size))
-                    * (self.config.step_size * self.config.step_size)
-                    * self.wavefunction[i],
-        );
+        let next = if self.config.using_numerov {
+            numerov_step(
+                self.wavefunction[i],
+                self.wavefunction[i - 1],
+                self.k_sqr(i + 1),
+                self.k_sqr(i),
+                self.k_sqr(i - 1),
+                self.config.step_size,
+            )
+        } else {
+            second_difference_step(
+                self.wavefunction[i],
+                self.wavefunction[i - 1],
+                self.k_sqr(i),
+                self.config.step_size,
+            )
+        };
+        self.wavefunction.push(next);
     }
 
     /// Determines if the wavefunction is diverging to infinity (positive or negative).
@@ -50,25 +98,48 @@ impl ShootingSolver {
     /// Approximates the wavefunction for the current energy. Stops when it has
     /// computed the requested number of steps, or if the wavefunction begins
     /// diverging.
-    fn compute_wavefunction(&mut self) {
+    fn compute_wavefunction(&mut self) -> Result<(), Error> {
         self.reset_wavefunction();
 
-        let steps = (self.config.x_max / self.config.step_size).round() as usize + 1;
-        for _ in 2..=steps {
+        for _ in 2..=self.steps() {
             if self.is_diverging() {
                 break;
             }
             self.step();
         }
+
+        if self.wavefunction.last().unwrap().is_finite() {
+            Ok(())
+        } else {
+            Err(Error::NumericalOverflow)
+        }
     }
 
-    /// Resets the wavefunction vector.
+    /// Resets the wavefunction vector. When integrating from `x_min`, the second
+    /// value is tiny so that growth through a classically forbidden region near
+    /// `x_min` does not trip the divergence check.
     fn reset_wavefunction(&mut self) {
         self.wavefunction.clear();
+        if self.config.x_min.is_some() {
+            self.wavefunction.push(0.0);
+            self.wavefunction.push(1e-18 * self.config.step_size);
+            return;
+        }
+
         match self.config.parity {
             Parity::Even => {
                 self.wavefunction.push(1.0);
-                self.wavefunction.push(1.0);
+                if self.config.using_numerov {
+                    // Numerov's method applied at x = 0 with ψ(-h) = ψ(h), which keeps the
+                    // start as accurate as the rest of the integration
+                    let h_sqr = self.config.step_size * self.config.step_size;
+                    self.wavefunction.push(
+                        (1.0 - (5.0 / 12.0) * h_sqr * self.k_sqr(0))
+                            / (1.0 + (1.0 / 12.0) * h_sqr * self.k_sqr(1)),
+                    );
+                } else {
+                    self.wavefunction.push(1.0);
+                }
             }
             Parity::Odd => {
                 self.wavefunction.push(0.0);
@@ -76,13 +147,81 @@ impl ShootingSolver {
             }
         }
     }
+
+    /// Searches for the level by adjusting the energy until the tail of the
+    /// wavefunction stops changing sign. If `level` holds the number of nodes and an
+    /// energy interval containing the level, the search starts from the middle of
+    /// the interval instead of `initial_energy`, and the width of the interval is
+    /// the energy error.
+    fn search(
+        &mut self,
+        observer: &mut dyn Observer,
+        level: Option<(usize, (f64, f64))>,
+    ) -> Result<Solution<ShootingConfig<P>>, Error> {
+        if let Some((_, (lower, upper))) = level {
+            self.set_initial_energy(0.5 * (lower + upper), 0.0);
+        }
+
+        for iteration in 0..self.config.max_iterations {
+            self.compute_wavefunction()?;
+            let progress = Progress {
+                step_size: Some(self.energy_step_size),
+                ..Progress::new(iteration, self.energy)
+            };
+            if observer.observe(&progress) == Control::Stop {
+                return Err(Error::Cancelled {
+                    iterations: iteration + 1,
+                });
+            }
+
+            if self.energy_step_size.abs() <= self.config.energy_step_size_cutoff {
+                // The parity only applies to the symmetric mode
+                let parity = match self.config.x_min {
+                    Some(_) => None,
+                    None => Some(self.config.parity),
+                };
+                let mut solution = Solution {
+                    quantum_number: level.map(|(n, _)| n),
+                    parity,
+                    convergence: Convergence {
+                        iterations: iteration + 1,
+                        energy_error: Some(match level {
+                            Some((_, (lower, upper))) => upper - lower,
+                            None => self.energy_step_size.abs(),
+                        }),
+                    },
+                    quadrature: self.config.quadrature,
+                    ..Solution::new(&self.wavefunction_points(), self.energy, &self.config)
+                };
+                truncate_tails(&mut solution.psi);
+                solution.normalize();
+                return Ok(solution);
+            }
+
+            if self.wavefunction.last().unwrap() * self.last_diverge < 0.0 {
+                self.energy_step_size = -self.energy_step_size / 2.0;
+            }
+
+            self.energy += self.energy_step_size;
+            self.last_diverge = if self.wavefunction.last().unwrap() >= &0.0 {
+                1.0
+            } else {
+                -1.0
+            };
+        }
+
+        Err(Error::NonConvergence {
+            iterations: self.config.max_iterations,
+        })
+    }
 }
 
-impl Solver for ShootingSolver {
-    type CONFIG = ShootingConfig;
+impl<P: Potential + Clone> Solver for ShootingSolver<P> {
+    type CONFIG = ShootingConfig<P>;
     
     fn new(config: &Self::CONFIG) -> Self {
-        let steps = (config.x_max / config.step_size).round() as usize + 1;
+        let x_start = config.x_min.unwrap_or(0.0);
+        let steps = ((config.x_max - x_start) / config.step_size).round() as usize + 1;
         let wavefunction: Vec<f64> = Vec::with_capacity(steps);
 
         ShootingSolver {
@@ -97,27 +236,39 @@ impl Solver for ShootingSolver {
     /// Popuplates the wavefunction vector with a solution to the Schrodinger equation
     /// and also determines the corresponding energy. The process requires iterating
     /// over many candidate energies and stopping when the energy step size becomes
-    /// sufficiently small.
-    fn solve(&mut self) {
-        loop {
-            self.compute_wavefunction();
-            if self.energy_step_size.abs() <= self.config.energy_step_size_cutoff {
-                break;
-            }
+    /// sufficiently small. If a quantum number is requested, the energy is first
+    /// bracketed using the node theorem, so that the requested state is guaranteed
+    /// to be found. The returned wavefunction has its diverging tails set to zero
+    /// and is normalized so that ∫|ψ|²dx = 1.
+    fn solve(&mut self) -> Result<Solution<Self::CONFIG>, Error> {
+        self.solve_observed(&mut Silent)
+    }
 
-            if self.wavefunction.last().unwrap() * self.last_diverge < 0.0 {
-                self.energy_step_size = -self.energy_step_size / 2.0;
-            }
+    /// Reports the energy and energy step size for each candidate energy.
+    fn solve_observed(
+        &mut self,
+        observer: &mut dyn Observer,
+    ) -> Result<Solution<Self::CONFIG>, Error> {
+        self.validate()?;
 
-            self.energy += self.energy_step_size;
-            self.last_diverge = if self.wavefunction.last().unwrap() >= &0.0 {
-                1.0
-            } else {
-                -1.0
-            };
+        match self.config.quantum_number {
+            Some(n) => {
+                let bracket = self.bracket_level(n)?;
+                self.search(observer, Some((n, bracket)))
+            }
+            None => self.search(observer, None),
         }
     }
 
+    fn solve_from_bracket(
+        &mut self,
+        nodes: usize,
+        bracket: (f64, f64),
+    ) -> Result<Solution<Self::CONFIG>, Error> {
+        self.validate()?;
+        self.search(&mut Silent, Some((nodes, bracket)))
+    }
+
     fn energy(&self) -> f64 {
         self.energy
     }
@@ -127,6 +278,15 @@ impl Solver for ShootingSolver {
     }
 
     fn wavefunction_points(&self) -> Vec<(f64, f64)> {
+        if self.config.x_min.is_some() {
+            return self
+                .wavefunction
+                .iter()
+                .enumerate()
+                .map(|(i, psi)| (self.x_from_index(i), *psi))
+                .collect();
+        }
+
         let mut x_vals: Vec<f64> = Vec::new();
         let mut psi_vals: Vec<f64> = Vec::new();
         let mut pairs: Vec<(f64, f64)> = Vec::new();
@@ -148,12 +308,317 @@ impl Solver for ShootingSolver {
         }
         pairs
     }
+
+    /// Checks that the configuration describes a usable grid and that a requested
+    /// quantum number matches the parity.
+    fn validate(&self) -> Result<(), Error> {
+        let config = &self.config;
+        if config.step_size <= 0.0 {
+            return Err(Error::InvalidConfig("step_size must be positive".to_string()));
+        }
+        match config.x_min {
+            Some(x_min) if x_min >= config.x_max => {
+                return Err(Error::InvalidConfig(
+                    "x_min must be less than x_max".to_string(),
+                ));
+            }
+            None if config.x_max <= 0.0 => {
+                return Err(Error::InvalidConfig("x_max must be positive".to_string()));
+            }
+            _ => {}
+        }
+        if self.steps() < 3 {
+            return Err(Error::StepSizeTooLarge {
+                step_size: config.step_size,
+            });
+        }
+        if let (None, Some(n)) = (config.x_min, config.quantum_number) {
+            let parity_matches = match config.parity {
+                Parity::Even => n % 2 == 0,
+                Parity::Odd => n % 2 == 1,
+            };
+            if !parity_matches {
+                return Err(Error::InvalidConfig(format!(
+                    "quantum number {n} does not match the parity of the solver"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn energy_lower_bound(&self) -> f64 {
+        (0..self.steps())
+            .map(|i| self.config.potential.value(self.x_from_index(i)))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Integrates the wavefunction at the given energy and, unless integrating from
+    /// `x_min`, mirrors it according to the parity, so that the nodes are counted
+    /// over the whole domain. This overwrites the current wavefunction.
+    fn trial_wavefunction(&mut self, energy: f64) -> Option<Vec<f64>> {
+        self.energy = energy;
+        self.compute_wavefunction().ok()?;
+
+        Some(
+            self.wavefunction_points()
+                .iter()
+                .map(|(_, psi)| *psi)
+                .collect(),
+        )
+    }
+
+    fn set_initial_energy(&mut self, energy: f64, energy_step_size: f64) {
+        self.energy = energy;
+        self.energy_step_size = energy_step_size;
+        self.last_diverge = 0.0;
+    }
+
+    fn energy_tolerance(&self) -> f64 {
+        self.config.energy_step_size_cutoff
+    }
 }
 
 /// The parity of solutions that a solver will look for when solving
 /// the Schrodinger equation.
-#[derive(Clone, Copy)]
+/// Sets the diverging tails of ψ to zero. Toward either end of the grid, |ψ|
+/// decays until the integration picks up the solution that grows in the
+/// classically forbidden region, so everything beyond the smallest |ψ| before
+/// that growth is discarded.
+fn truncate_tails(psi: &mut [f64]) {
+    let mut end = psi.len() - 1;
+    while end > 0 && psi[end - 1].abs() < psi[end].abs() {
+        end -= 1;
+    }
+    psi[end + 1..].fill(0.0);
+
+    let mut start = 0;
+    while start < end && psi[start + 1].abs() < psi[start].abs() {
+        start += 1;
+    }
+    psi[..start].fill(0.0);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Parity {
     Even,
     Odd,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::physics::analytic::ExactSolution;
+    use crate::physics::potentials::HarmonicOscillator;
+    use crate::physics::solvers::matching::{MatchingConfig, MatchingSolver};
+    use crate::physics::solvers::shooting::*;
+    use crate::physics::{harmonic_potential, lennard_jones_potential};
+    use crate::utils::integration::Quadrature;
+
+    /// The even states of the harmonic oscillator on [-6, 6], which the tests vary
+    /// with `..config`.
+    fn harmonic_config() -> ShootingConfig<fn(f64) -> f64> {
+        ShootingConfig {
+            x_min: None,
+            x_max: 6.0,
+            step_size: 0.001,
+            initial_energy: 0.0,
+            intitial_energy_step_size: 0.1,
+            wavefunction_cutoff: 100.0,
+            potential: harmonic_potential,
+            energy_step_size_cutoff: 1e-8,
+            max_iterations: 10000,
+            parity: Parity::Even,
+            using_numerov: true,
+            quantum_number: None,
+            quadrature: Quadrature::Trapezoidal,
+        }
+    }
+
+    #[test]
+    fn spectrum() {
+        let mut solver = ShootingSolver::new(&ShootingConfig {
+            parity: Parity::Odd,
+            using_numerov: false,
+            ..harmonic_config()
+        });
+        let states = solver.solve_states(3).unwrap();
+
+        assert_eq!(states.len(), 3);
+        for (k, state) in states.iter().enumerate() {
+            assert_eq!(state.nodes, 2 * k + 1);
+            assert!((state.energy - (2.0 * k as f64 + 1.5)).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn requested_quantum_number() {
+        let mut solver = ShootingSolver::new(&ShootingConfig {
+            using_numerov: false,
+            quantum_number: Some(4),
+            ..harmonic_config()
+        });
+        solver.solve().unwrap();
+        assert!((solver.energy() - 4.5).abs() < 1e-3);
+
+        // A requested quantum number does not restrict the spectrum
+        let states = solver.solve_states(2).unwrap();
+        for (k, state) in states.iter().enumerate() {
+            assert_eq!(state.nodes, 2 * k);
+            assert!((state.energy - (2.0 * k as f64 + 0.5)).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn numerov() {
+        let config = ShootingConfig {
+            step_size: 0.05,
+            energy_step_size_cutoff: 1e-10,
+            quantum_number: Some(2),
+            ..harmonic_config()
+        };
+        let mut numerov_solver = ShootingSolver::new(&config);
+        numerov_solver.solve().unwrap();
+        let numerov_error = (numerov_solver.energy() - 2.5).abs();
+
+        let mut second_difference_solver = ShootingSolver::new(&ShootingConfig {
+            using_numerov: false,
+            ..config
+        });
+        second_difference_solver.solve().unwrap();
+        let second_difference_error = (second_difference_solver.energy() - 2.5).abs();
+
+        assert!(numerov_error < 1e-5);
+        assert!(numerov_error < 0.01 * second_difference_error);
+    }
+
+    #[test]
+    fn two_sided() {
+        let mut solver = ShootingSolver::new(&ShootingConfig {
+            x_min: Some(-5.0),
+            x_max: 7.0,
+            step_size: 0.01,
+            initial_energy: 0.0,
+            intitial_energy_step_size: 0.1,
+            wavefunction_cutoff: 100.0,
+            potential: harmonic_potential.translated(1.0),
+            energy_step_size_cutoff: 1e-10,
+            max_iterations: 10000,
+            parity: Parity::Even,
+            using_numerov: true,
+            quantum_number: None,
+            quadrature: Quadrature::Trapezoidal,
+        });
+        let states = solver.solve_states(3).unwrap();
+        for (n, state) in states.iter().enumerate() {
+            assert_eq!(state.nodes, n);
+            assert!((state.energy - (n as f64 + 0.5)).abs() < 1e-6);
+        }
+
+        let mut shooting_solver = ShootingSolver::new(&ShootingConfig {
+            x_min: Some(0.8),
+            energy_step_size_cutoff: 1e-10,
+            potential: lennard_jones_potential as fn(f64) -> f64,
+            quantum_number: Some(0),
+            ..harmonic_config()
+        });
+        shooting_solver.solve().unwrap();
+        let ground_state_energy = shooting_solver.energy();
+
+        // Walking up from below the ground state finds the same level
+        shooting_solver.config.quantum_number = None;
+        shooting_solver.config.initial_energy = -5.0;
+        shooting_solver.reset();
+        shooting_solver.solve().unwrap();
+        assert!((shooting_solver.energy() - ground_state_energy).abs() < 1e-8);
+
+        let mut matching_solver = MatchingSolver::new(&MatchingConfig {
+            x_min: 0.8,
+            x_max: 6.0,
+            x_match: 1.1,
+            step_size: 0.001,
+            initial_energy: 0.0,
+            initial_energy_step_size: 0.1,
+            energy_step_size_cutoff: 1e-10,
+            max_iterations: 10000,
+            potential: lennard_jones_potential,
+            using_numerov: true,
+            guarding_scale_factor: false,
+            quantum_number: Some(0),
+            quadrature: Quadrature::Trapezoidal,
+        });
+        matching_solver.solve().unwrap();
+        assert!((shooting_solver.energy() - matching_solver.energy()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn solutions_outlive_solver() {
+        let mut matching_solver = MatchingSolver::new(&MatchingConfig {
+            x_min: -6.0,
+            x_max: 6.0,
+            x_match: 0.7,
+            step_size: 0.01,
+            initial_energy: 0.0,
+            initial_energy_step_size: 0.1,
+            energy_step_size_cutoff: 1e-8,
+            max_iterations: 10000,
+            potential: harmonic_potential,
+            using_numerov: true,
+            guarding_scale_factor: false,
+            quantum_number: Some(2),
+            quadrature: Quadrature::Trapezoidal,
+        });
+        let matching_solution = matching_solver.solve().unwrap();
+        matching_solver.reset();
+        assert!(matching_solver.energy() != matching_solution.energy);
+        assert_eq!(matching_solution.quantum_number, Some(2));
+        assert!(matching_solution.convergence.iterations > 0);
+        assert!((matching_solution.norm() - 1.0).abs() < 1e-6);
+
+        let shooting_solution = ShootingSolver::new(&ShootingConfig {
+            step_size: 0.01,
+            quantum_number: Some(2),
+            ..harmonic_config()
+        })
+        .solve()
+        .unwrap();
+        assert!((shooting_solution.norm() - 1.0).abs() < 1e-6);
+        assert_eq!(shooting_solution.parity, Some(Parity::Even));
+        assert!((shooting_solution.energy - matching_solution.energy).abs() < 1e-6);
+
+        // The same wavefunction up to sign
+        let sign = (shooting_solution.interpolate(0.0) * matching_solution.interpolate(0.0)).signum();
+        for x in [-2.5, -1.0, 0.3, 1.7] {
+            let difference =
+                shooting_solution.interpolate(x) - sign * matching_solution.interpolate(x);
+            assert!(difference.abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn normalized_states() {
+        let oscillator = HarmonicOscillator { omega: 1.0 };
+        for (n, parity, quadrature) in [
+            (0, Parity::Even, Quadrature::Trapezoidal),
+            (1, Parity::Odd, Quadrature::Simpson),
+        ] {
+            let solution = ShootingSolver::new(&ShootingConfig {
+                x_max: 7.0,
+                step_size: 0.01,
+                parity,
+                quantum_number: Some(n),
+                quadrature,
+                ..harmonic_config()
+            })
+            .solve()
+            .unwrap();
+            assert_eq!(solution.quadrature, quadrature);
+            assert!((solution.norm() - 1.0).abs() < 1e-6);
+
+            // The same wavefunction up to sign, which is positive at x = 1 for both states
+            let exact = oscillator.wavefunction_points(n, &solution.x).unwrap();
+            let sign = solution.interpolate(1.0).signum();
+            for ((_, psi), (_, expected)) in solution.points().iter().zip(exact.iter()) {
+                assert!((sign * psi - expected).abs() < 1e-3);
+            }
+        }
+    }
+}
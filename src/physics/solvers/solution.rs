@@ -0,0 +1,262 @@
+//! The result of a successful `Solver::solve`, owned by the caller. Unlike the
+//! state inside a solver, a solution is not affected by `reset` or by solving
+//! again, so solutions from different solvers or configurations can be kept side
+//! by side and compared.
+
+use std::io::{self, BufRead, Write};
+
+use crate::physics::solvers::shooting::Parity;
+use crate::utils::integration::Quadrature;
+
+/// How a solver arrived at a solution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Convergence {
+    /// The number of iterations taken, or zero for direct methods.
+    pub iterations: usize,
+    /// An estimate of the uncertainty of the energy, if the method provides one:
+    /// the final energy step of the matching and shooting methods, or the
+    /// statistical error of the Monte Carlo methods.
+    pub energy_error: Option<f64>,
+}
+
+/// An energy level and its wavefunction on a uniform grid.
+#[derive(Clone, Debug)]
+pub struct Solution<C> {
+    /// The grid points, in increasing order.
+    pub x: Vec<f64>,
+    /// The wavefunction values at the grid points.
+    pub psi: Vec<f64>,
+    pub energy: f64,
+    /// The number of nodes of the wavefunction, if the solver knows which level
+    /// it found.
+    pub quantum_number: Option<usize>,
+    pub parity: Option<Parity>,
+    pub convergence: Convergence,
+    /// The rule that `norm` and `normalize` integrate with, taken from the
+    /// configuration of solvers that have one.
+    pub quadrature: Quadrature,
+    /// The configuration of the solver that found this solution.
+    pub config: C,
+}
+
+impl<C: Clone> Solution<C> {
+    /// Returns a solution for the given (x, ψ) points, with no quantum number,
+    /// parity or convergence information, integrated with the trapezoidal rule.
+    pub fn new(points: &[(f64, f64)], energy: f64, config: &C) -> Self {
+        let (x, psi) = points.iter().cloned().unzip();
+        Solution {
+            x,
+            psi,
+            energy,
+            quantum_number: None,
+            parity: None,
+            convergence: Convergence {
+                iterations: 0,
+                energy_error: None,
+            },
+            quadrature: Quadrature::default(),
+            config: config.clone(),
+        }
+    }
+}
+
+impl<C> Solution<C> {
+    /// Returns the same solution with a different configuration attached. Use
+    /// `with_config(())` to drop the configuration.
+    pub fn with_config<D>(self, config: D) -> Solution<D> {
+        Solution {
+            x: self.x,
+            psi: self.psi,
+            energy: self.energy,
+            quantum_number: self.quantum_number,
+            parity: self.parity,
+            convergence: self.convergence,
+            quadrature: self.quadrature,
+            config,
+        }
+    }
+
+    /// Returns the (x, ψ) points of the wavefunction.
+    pub fn points(&self) -> Vec<(f64, f64)> {
+        self.x.iter().cloned().zip(self.psi.iter().cloned()).collect()
+    }
+
+    /// Returns the spacing of the grid, or zero if it has fewer than two points.
+    pub fn step_size(&self) -> f64 {
+        if self.x.len() < 2 {
+            return 0.0;
+        }
+        self.x[1] - self.x[0]
+    }
+
+    /// Returns ∫|ψ|²dx over the grid.
+    pub fn norm(&self) -> f64 {
+        let density: Vec<f64> = self.psi.iter().map(|val| val * val).collect();
+        self.quadrature.integrate(&density, self.step_size())
+    }
+
+    /// Scales the wavefunction so that ∫|ψ|²dx = 1.
+    pub fn normalize(&mut self) {
+        let scale = self.norm().sqrt().recip();
+        self.psi.iter_mut().for_each(|val| *val *= scale);
+    }
+
+    /// Returns ψ(x), linearly interpolated between the grid points. The
+    /// wavefunction is taken to vanish outside of the grid.
+    pub fn interpolate(&self, x: f64) -> f64 {
+        let n = self.x.len();
+        if n == 0 || x < self.x[0] || x > self.x[n - 1] {
+            return 0.0;
+        }
+        if n == 1 {
+            return self.psi[0];
+        }
+
+        let i = (((x - self.x[0]) / self.step_size()) as usize).min(n - 2);
+        let t = (x - self.x[i]) / (self.x[i + 1] - self.x[i]);
+        (1.0 - t) * self.psi[i] + t * self.psi[i + 1]
+    }
+
+    /// Writes the solution as text that gnuplot can plot directly. A header of
+    /// '#' comment lines holds the energy, quantum number, parity and convergence
+    /// information, and each following line is an x value and a ψ value separated
+    /// by a space. The configuration and quadrature rule are not written.
+    /// # Example
+    /// ```txt
+    /// # energy 2.5
+    /// # quantum_number 2
+    /// # parity even
+    /// # iterations 41
+    /// # energy_error 0.0000000074505805969238
+    /// -6 0.0000000000000012
+    /// -5.99 0.0000000000000013
+    ///   ︙             ︙
+    /// ```
+    pub fn write_data(&self, writer: &mut impl Write) -> io::Result<()> {
+        let optional = |value: Option<String>| value.unwrap_or_else(|| "none".to_string());
+
+        writeln!(writer, "# energy {}", self.energy)?;
+        writeln!(
+            writer,
+            "# quantum_number {}",
+            optional(self.quantum_number.map(|n| n.to_string()))
+        )?;
+        let parity = self.parity.map(|parity| match parity {
+            Parity::Even => "even".to_string(),
+            Parity::Odd => "odd".to_string(),
+        });
+        writeln!(writer, "# parity {}", optional(parity))?;
+        writeln!(writer, "# iterations {}", self.convergence.iterations)?;
+        writeln!(
+            writer,
+            "# energy_error {}",
+            optional(self.convergence.energy_error.map(|err| err.to_string()))
+        )?;
+
+        for (x, psi) in self.x.iter().zip(self.psi.iter()) {
+            writeln!(writer, "{} {}", x, psi)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Solution<()> {
+    /// Reads a solution in the format written by `write_data`. The solution has no
+    /// configuration attached.
+    pub fn read_data(reader: impl BufRead) -> io::Result<Self> {
+        let invalid = |line: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid solution data: {line}"),
+            )
+        };
+
+        let mut solution = Solution::new(&[], f64::NAN, &());
+        for line in reader.lines() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('#') {
+                let mut words = header.split_whitespace();
+                let (key, value) = match (words.next(), words.next()) {
+                    (Some(key), Some(value)) => (key, value),
+                    _ => return Err(invalid(line)),
+                };
+                let number = |value: &str| value.parse::<f64>().map_err(|_| invalid(line));
+
+                match (key, value) {
+                    ("energy", value) => solution.energy = number(value)?,
+                    (_, "none") => {}
+                    ("quantum_number", value) => {
+                        solution.quantum_number = Some(value.parse().map_err(|_| invalid(line))?)
+                    }
+                    ("parity", "even") => solution.parity = Some(Parity::Even),
+                    ("parity", "odd") => solution.parity = Some(Parity::Odd),
+                    ("iterations", value) => {
+                        solution.convergence.iterations = value.parse().map_err(|_| invalid(line))?
+                    }
+                    ("energy_error", value) => {
+                        solution.convergence.energy_error = Some(number(value)?)
+                    }
+                    _ => return Err(invalid(line)),
+                }
+                continue;
+            }
+
+            let mut values = line.split_whitespace().map(|value| value.parse::<f64>());
+            match (values.next(), values.next(), values.next()) {
+                (Some(Ok(x)), Some(Ok(psi)), None) => {
+                    solution.x.push(x);
+                    solution.psi.push(psi);
+                }
+                _ => return Err(invalid(line)),
+            }
+        }
+
+        Ok(solution)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::physics::solvers::solution::*;
+
+    #[test]
+    fn normalize_interpolate_round_trip() {
+        let points: Vec<(f64, f64)> = (0..=200)
+            .map(|i| {
+                let x = -5.0 + 0.05 * i as f64;
+                (x, 3.0 * (-0.5 * x * x).exp())
+            })
+            .collect();
+        let mut solution = Solution::new(&points, 0.5, &());
+        solution.quantum_number = Some(0);
+        solution.parity = Some(Parity::Even);
+        solution.convergence.iterations = 12;
+
+        solution.normalize();
+        assert!((solution.norm() - 1.0).abs() < 1e-12);
+
+        // Exact at the grid points, and close to ψ₀ in between
+        let psi_0 = |x: f64| std::f64::consts::PI.powf(-0.25) * (-0.5 * x * x).exp();
+        assert!((solution.interpolate(-5.0) - solution.psi[0]).abs() < 1e-15);
+        assert!((solution.interpolate(0.325) - psi_0(0.325)).abs() < 1e-3);
+        assert_eq!(solution.interpolate(5.1), 0.0);
+
+        let mut data = Vec::new();
+        solution.write_data(&mut data).unwrap();
+        let read = Solution::read_data(&data[..]).unwrap();
+        assert_eq!(read.energy, solution.energy);
+        assert_eq!(read.quantum_number, Some(0));
+        assert_eq!(read.parity, Some(Parity::Even));
+        assert_eq!(read.convergence, solution.convergence);
+        assert_eq!(read.x, solution.x);
+        assert_eq!(read.psi, solution.psi);
+
+        assert!(Solution::read_data(&b"# energy\n"[..]).is_err());
+    }
+}
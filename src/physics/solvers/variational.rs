@@ -1,42 +1,97 @@
-use crate::physics::solvers::Solver;
+use crate::error::Error;
+use crate::physics::potentials::Potential;
+use crate::physics::solvers::observer::{Control, Observer, Progress, Silent};
+use crate::physics::solvers::solution::{Convergence, Solution};
+use crate::physics::solvers::{Eigenpair, Solver};
 use crate::utils::finite_difference;
 use crate::utils::finite_difference::SecondDerivateMethod;
-use crate::utils::integration;
+use crate::utils::integration::Quadrature;
 
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// How the largest change made to the wavefunction in one step evolves over the
+/// iterations.
+#[derive(Clone, Copy, Debug)]
+pub enum StepSchedule {
+    /// The same maximum change on every iteration.
+    Constant { max_delta: f64 },
+    /// Annealing: the maximum change starts at `initial` and is multiplied by
+    /// `decay` every `interval` iterations.
+    Exponential {
+        initial: f64,
+        decay: f64,
+        interval: usize,
+    },
+}
+
+impl StepSchedule {
+    /// Returns the maximum change for the given iteration, counting from zero.
+    pub fn max_delta(&self, iteration: usize) -> f64 {
+        match *self {
+            StepSchedule::Constant { max_delta } => max_delta,
+            StepSchedule::Exponential {
+                initial,
+                decay,
+                interval,
+            } => initial * decay.powi((iteration / interval.max(1)) as i32),
+        }
+    }
+}
 
 #[derive(Clone)]
-pub struct VariationalConfig {
+pub struct VariationalConfig<P, F> {
     pub x_min: f64,
     pub x_max: f64,
     pub step_size: f64,
-    pub potential: fn(f64) -> f64,
+    pub potential: P,
+    /// The initial guess ψ(x), which does not need to be normalized.
+    pub initial_wavefunction: F,
+    /// The seed of the random number generator. The same seed always gives the
+    /// same result.
+    pub seed: u64,
+    /// The maximum number of random changes tried.
+    pub max_iterations: usize,
+    pub schedule: StepSchedule,
+    /// If set, the search stops once a sweep of as many iterations as there are
+    /// grid points lowers the energy by less than this, and fails if that does not
+    /// happen within `max_iterations`. Otherwise all `max_iterations` are run.
+    pub tolerance: Option<f64>,
+    /// The rule used for the integrals of the energy and the normalization.
+    pub quadrature: Quadrature,
 }
 
-pub struct VariationalSolver {
-    pub config: VariationalConfig,
+pub struct VariationalSolver<P, F> {
+    pub config: VariationalConfig<P, F>,
     steps: usize,
     energy: f64,
     wavefunction: Vec<f64>,
     last_energy: Option<f64>,
+    rng: StdRng,
 }
 
-impl VariationalSolver {
-    fn step(&mut self) {
+impl<P, F> VariationalSolver<P, F>
+where
+    P: Potential + Clone,
+    F: Fn(f64) -> f64 + Clone,
+{
+    /// Tries a random change to one ψ value and keeps it if it lowers the energy.
+    /// Returns whether the change was kept.
+    fn step(&mut self, max_delta: f64) -> bool {
         let mut candidate: Vec<f64> = self.wavefunction.iter().cloned().collect();
 
-        let index = rand::thread_rng().gen_range(0, self.steps);
+        let index = self.rng.gen_range(0, self.steps);
 
-        let max_delta = 0.01;
-        let psi_delta = rand::thread_rng().gen_range(-max_delta, max_delta);
+        let psi_delta = self.rng.gen_range(-max_delta, max_delta);
         candidate[index] += psi_delta;
 
         let candidate_energy = energy_of(
             &candidate,
             self.steps,
             self.config.step_size,
-            self.config.potential,
+            &self.config.potential,
             self.config.x_min,
+            self.config.quadrature,
         );
         if candidate_energy < self.energy {
             self.last_energy = Some(self.energy);
@@ -49,14 +104,15 @@ impl VariationalSolver {
             //);
             self.energy = candidate_energy;
             self.wavefunction = candidate;
-            println!("{}", self.energy);
+            return true;
         }
+        false
     }
 
     fn normalize(&mut self) {
         let (_, mut f): (Vec<_>, Vec<_>) = self.wavefunction_points().iter().cloned().unzip();
         f = f.iter().map(|val| val * val).collect();
-        let integral = integration::trapezoidal(&f, &self.config.step_size);
+        let integral = self.config.quadrature.integrate(&f, self.config.step_size);
         self.wavefunction
             .iter_mut()
             .for_each(|val| *val = *val * (1.0 / integral).sqrt());
@@ -73,21 +129,23 @@ impl VariationalSolver {
     }
 }
 
-impl Solver for VariationalSolver {
-    type CONFIG = VariationalConfig;
+impl<P, F> Solver for VariationalSolver<P, F>
+where
+    P: Potential + Clone,
+    F: Fn(f64) -> f64 + Clone,
+{
+    type CONFIG = VariationalConfig<P, F>;
 
+    /// Samples the initial guess on the grid, normalized so that ∫|ψ|²dx = 1.
     fn new(config: &Self::CONFIG) -> Self {
         let steps = ((config.x_max - config.x_min) / config.step_size).round() as usize + 1;
-        let mut wavefunction = vec![(1.0 / (config.x_max - config.x_min)).sqrt(); steps];
-        for (i, val) in wavefunction.iter_mut().enumerate() {
-            let x = x_from_index(i, config.x_min, config.step_size);
-            if x < -1.0 || x > 1.0 {
-                *val = 0.0;
-            }
-        }
-        //for (i, psi) in wavefunction.iter_mut().enumerate() {
-        //*psi = (i as f64).powf(2.0) * 4e-7;
-        //}
+        let mut wavefunction: Vec<f64> = (0..steps)
+            .map(|i| (config.initial_wavefunction)(x_from_index(i, config.x_min, config.step_size)))
+            .collect();
+        let density: Vec<f64> = wavefunction.iter().map(|val| val * val).collect();
+        let norm = config.quadrature.integrate(&density, config.step_size).sqrt();
+        wavefunction.iter_mut().for_each(|val| *val /= norm);
+
         VariationalSolver {
             config: config.clone(),
             steps,
@@ -95,22 +153,65 @@ impl Solver for VariationalSolver {
                 &wavefunction,
                 steps,
                 config.step_size,
-                config.potential,
+                &config.potential,
                 config.x_min,
+                config.quadrature,
             ),
             wavefunction,
             last_energy: None,
+            rng: StdRng::seed_from_u64(config.seed),
         }
     }
 
-    fn solve(&mut self) {
-        for i in 1..=100000 {
-            if i % 10000 == 0 {
-                println!("{i}, {}", self.energy)
+    fn solve(&mut self) -> Result<Solution<Self::CONFIG>, Error> {
+        self.solve_observed(&mut Silent)
+    }
+
+    /// Reports the energy, the largest allowed change in ψ and whether the change
+    /// was accepted for each iteration.
+    fn solve_observed(
+        &mut self,
+        observer: &mut dyn Observer,
+    ) -> Result<Solution<Self::CONFIG>, Error> {
+        if self.config.max_iterations == 0 {
+            return Err(Error::InvalidConfig(
+                "max_iterations must be at least 1".to_string(),
+            ));
+        }
+
+        let mut sweep_energy = self.energy;
+        let mut converged = self.config.tolerance.is_none();
+        let mut iterations = 0;
+        for i in 0..self.config.max_iterations {
+            iterations = i + 1;
+            let max_delta = self.config.schedule.max_delta(i);
+            let accepted = self.step(max_delta);
+            let progress = Progress {
+                step_size: Some(max_delta),
+                accepted: Some(accepted),
+                ..Progress::new(i, self.energy)
             };
-            self.step();
+            if observer.observe(&progress) == Control::Stop {
+                return Err(Error::Cancelled { iterations: i + 1 });
+            }
+
+            if let Some(tolerance) = self.config.tolerance {
+                if (i + 1) % self.steps == 0 {
+                    if sweep_energy - self.energy < tolerance {
+                        converged = true;
+                        break;
+                    }
+                    sweep_energy = self.energy;
+                }
+            }
         }
         self.normalize();
+
+        if !converged {
+            return Err(Error::NonConvergence {
+                iterations: self.config.max_iterations,
+            });
+        }
         //self.energy = energy_of(
         //&self.wavefunction,
         //self.steps,
@@ -118,6 +219,15 @@ impl Solver for VariationalSolver {
         //self.potential,
         //self.x_min,
         //);
+        Ok(Solution {
+            quantum_number: Some(0),
+            convergence: Convergence {
+                iterations,
+                energy_error: None,
+            },
+            quadrature: self.config.quadrature,
+            ..Solution::new(&self.wavefunction_points(), self.energy(), &self.config)
+        })
     }
 
     fn reset(&mut self) {
@@ -129,8 +239,9 @@ impl Solver for VariationalSolver {
             &self.wavefunction,
             self.steps,
             self.config.step_size,
-            self.config.potential,
+            &self.config.potential,
             self.config.x_min,
+            self.config.quadrature,
         )
     }
 
@@ -143,6 +254,33 @@ impl Solver for VariationalSolver {
 
         pairs
     }
+
+    fn energy_lower_bound(&self) -> f64 {
+        (0..self.steps)
+            .map(|i| {
+                self.config.potential.value(x_from_index(
+                    i,
+                    self.config.x_min,
+                    self.config.step_size,
+                ))
+            })
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// The variational method only converges to the ground state, so at most one
+    /// level is returned.
+    fn solve_states(&mut self, n: usize) -> Result<Vec<Eigenpair>, Error> {
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+
+        self.solve()?;
+        Ok(vec![Eigenpair {
+            energy: self.energy(),
+            nodes: 0,
+            wavefunction: self.wavefunction_points(),
+        }])
+    }
 }
 
 fn x_from_index(i: usize, x_min: f64, step_size: f64) -> f64 {
@@ -153,7 +291,7 @@ fn hamiltonian_on_wavefunction(
     wavefunction: &[f64],
     steps: usize,
     step_size: f64,
-    potential: fn(f64) -> f64,
+    potential: &impl Potential,
     x_min: f64,
 ) -> Vec<f64> {
     let mut result = Vec::with_capacity(steps);
@@ -168,7 +306,7 @@ fn hamiltonian_on_wavefunction(
         };
         result.push(
             -0.5 * finite_difference::second_derivative(&method, &wavefunction, i, step_size)
-                + potential(x_from_index(i, x_min, step_size)) * wavefunction[i],
+                + potential.value(x_from_index(i, x_min, step_size)) * wavefunction[i],
         );
     }
     result
@@ -178,8 +316,9 @@ pub fn energy_of(
     wavefunction: &[f64],
     steps: usize,
     step_size: f64,
-    potential: fn(f64) -> f64,
+    potential: &impl Potential,
     x_min: f64,
+    quadrature: Quadrature,
 ) -> f64 {
     let mut psi_hamil_psi = Vec::with_capacity(steps);
     let mut psi_psi = Vec::with_capacity(steps);
@@ -192,6 +331,50 @@ pub fn energy_of(
         psi_psi.push(psi * psi);
     }
 
-    integration::trapezoidal(&psi_hamil_psi, &step_size)
-        / integration::trapezoidal(&psi_psi, &step_size)
+    quadrature.integrate(&psi_hamil_psi, step_size) / quadrature.integrate(&psi_psi, step_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::physics::harmonic_potential;
+    use crate::physics::solvers::variational::*;
+
+    #[test]
+    fn seeded_runs_replay() {
+        let config = VariationalConfig {
+            x_min: -5.0,
+            x_max: 5.0,
+            step_size: 0.1,
+            potential: harmonic_potential,
+            initial_wavefunction: |x: f64| if x.abs() < 2.0 { 1.0 } else { 0.0 },
+            seed: 3,
+            max_iterations: 20000,
+            schedule: StepSchedule::Exponential {
+                initial: 0.05,
+                decay: 0.5,
+                interval: 5000,
+            },
+            tolerance: None,
+            quadrature: Quadrature::Trapezoidal,
+        };
+        let mut solver = VariationalSolver::new(&config);
+        let initial_energy = solver.energy();
+        solver.solve().unwrap();
+        let energy = solver.energy();
+        assert!(energy < initial_energy);
+
+        solver.reset();
+        solver.solve().unwrap();
+        assert_eq!(solver.energy(), energy);
+
+        assert!(matches!(
+            VariationalSolver::new(&VariationalConfig {
+                tolerance: Some(0.0),
+                max_iterations: 500,
+                ..config
+            })
+            .solve(),
+            Err(Error::NonConvergence { iterations: 500 })
+        ));
+    }
 }
@@ -0,0 +1,278 @@
+//! Variational Monte Carlo for estimating the energy of a trial wavefunction.
+//! Positions are sampled from |ψ_T|² with the Metropolis algorithm and the local
+//! energy E_L = Hψ_T/ψ_T is averaged over the samples. The average is an upper
+//! bound on the ground state energy, and its variance vanishes when ψ_T is an
+//! eigenstate.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::error::Error;
+use crate::physics::potentials::Potential;
+use crate::physics::solvers::observer::{Control, Observer, Progress, Silent};
+use crate::physics::solvers::solution::{Convergence, Solution};
+use crate::physics::solvers::Solver;
+use crate::utils::integration::Quadrature;
+use crate::utils::statistics::{blocking_error, mean};
+
+#[derive(Clone)]
+pub struct VariationalMonteCarloConfig<P, F> {
+    /// The grid that the trial wavefunction is returned on. The walk itself is not
+    /// restricted to it.
+    pub x_min: f64,
+    pub x_max: f64,
+    pub step_size: f64,
+    pub potential: P,
+    /// The trial wavefunction ψ_T(x), which does not need to be normalized.
+    pub trial_wavefunction: F,
+    pub initial_position: f64,
+    /// Proposed moves are drawn uniformly from [-move_size, move_size].
+    pub move_size: f64,
+    /// The number of moves made before samples are recorded.
+    pub thermalization_steps: usize,
+    pub samples: usize,
+    /// The number of consecutive samples averaged together when estimating the
+    /// statistical error.
+    pub block_size: usize,
+    pub seed: u64,
+    /// The rule used to normalize the trial wavefunction on the grid.
+    pub quadrature: Quadrature,
+}
+
+/// A solver that estimates the energy of a trial wavefunction by Metropolis
+/// sampling.
+pub struct VariationalMonteCarloSolver<P, F> {
+    pub config: VariationalMonteCarloConfig<P, F>,
+    steps: usize,
+    energy: f64,
+    error: f64,
+    acceptance_ratio: f64,
+}
+
+impl<P, F> VariationalMonteCarloSolver<P, F>
+where
+    P: Potential + Clone,
+    F: Fn(f64) -> f64 + Clone,
+{
+    /// Returns the statistical error (one standard deviation) of the energy.
+    pub fn error(&self) -> f64 {
+        self.error
+    }
+
+    /// Returns the fraction of proposed moves that were accepted.
+    pub fn acceptance_ratio(&self) -> f64 {
+        self.acceptance_ratio
+    }
+
+    fn x_from_index(&self, i: usize) -> f64 {
+        self.config.x_min + (i as f64) * self.config.step_size
+    }
+
+    /// Returns Hψ_T/ψ_T at x, with ψ_T'' approximated by a central difference.
+    fn local_energy(&self, x: f64) -> f64 {
+        let h = 1e-4;
+        let psi = &self.config.trial_wavefunction;
+        let second_derivative = (psi(x + h) - 2.0 * psi(x) + psi(x - h)) / (h * h);
+        -0.5 * second_derivative / psi(x) + self.config.potential.value(x)
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if self.config.move_size <= 0.0 {
+            return Err(Error::InvalidConfig(
+                "move_size must be positive".to_string(),
+            ));
+        }
+        if self.config.samples == 0 || self.config.block_size == 0 {
+            return Err(Error::InvalidConfig(
+                "samples and block_size must be at least 1".to_string(),
+            ));
+        }
+        if (self.config.trial_wavefunction)(self.config.initial_position) == 0.0 {
+            return Err(Error::InvalidConfig(
+                "the trial wavefunction must not vanish at initial_position".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl<P, F> Solver for VariationalMonteCarloSolver<P, F>
+where
+    P: Potential + Clone,
+    F: Fn(f64) -> f64 + Clone,
+{
+    type CONFIG = VariationalMonteCarloConfig<P, F>;
+
+    fn new(config: &Self::CONFIG) -> Self {
+        let steps = ((config.x_max - config.x_min) / config.step_size).round() as usize + 1;
+        VariationalMonteCarloSolver {
+            config: config.clone(),
+            steps,
+            energy: f64::NAN,
+            error: f64::NAN,
+            acceptance_ratio: f64::NAN,
+        }
+    }
+
+    /// Runs the Metropolis walk and averages the local energy. The same seed always
+    /// gives the same result.
+    fn solve(&mut self) -> Result<Solution<Self::CONFIG>, Error> {
+        self.solve_observed(&mut Silent)
+    }
+
+    /// Reports the local energy at the current position after every Metropolis
+    /// step, along with whether the proposed move was accepted.
+    fn solve_observed(
+        &mut self,
+        observer: &mut dyn Observer,
+    ) -> Result<Solution<Self::CONFIG>, Error> {
+        self.validate()?;
+
+        let mut rng = StdRng::seed_from_u64(self.config.seed);
+        let psi = &self.config.trial_wavefunction;
+        let mut x = self.config.initial_position;
+        let mut psi_sqr = psi(x) * psi(x);
+        let mut accepted = 0;
+        let mut local_energies = Vec::with_capacity(self.config.samples);
+
+        let total_steps = self.config.thermalization_steps + self.config.samples;
+        for i in 0..total_steps {
+            let candidate = x + rng.gen_range(-self.config.move_size, self.config.move_size);
+            let candidate_psi_sqr = psi(candidate) * psi(candidate);
+            let accept =
+                candidate_psi_sqr >= psi_sqr || rng.gen::<f64>() * psi_sqr < candidate_psi_sqr;
+            if accept {
+                x = candidate;
+                psi_sqr = candidate_psi_sqr;
+                accepted += 1;
+            }
+
+            let local_energy = self.local_energy(x);
+            if i >= self.config.thermalization_steps {
+                local_energies.push(local_energy);
+            }
+
+            let progress = Progress {
+                accepted: Some(accept),
+                ..Progress::new(i, local_energy)
+            };
+            if observer.observe(&progress) == Control::Stop {
+                return Err(Error::Cancelled { iterations: i + 1 });
+            }
+        }
+
+        self.energy = mean(&local_energies);
+        self.error = blocking_error(&local_energies, self.config.block_size);
+        self.acceptance_ratio = accepted as f64 / total_steps as f64;
+
+        if !self.energy.is_finite() {
+            return Err(Error::NumericalOverflow);
+        }
+        Ok(Solution {
+            convergence: Convergence {
+                iterations: total_steps,
+                energy_error: Some(self.error),
+            },
+            quadrature: self.config.quadrature,
+            ..Solution::new(&self.wavefunction_points(), self.energy, &self.config)
+        })
+    }
+
+    fn energy(&self) -> f64 {
+        self.energy
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(&self.config);
+    }
+
+    /// Returns the trial wavefunction on the grid, normalized so that ∫|ψ|²dx = 1.
+    fn wavefunction_points(&self) -> Vec<(f64, f64)> {
+        let psi: Vec<f64> = (0..self.steps)
+            .map(|i| (self.config.trial_wavefunction)(self.x_from_index(i)))
+            .collect();
+        let density: Vec<f64> = psi.iter().map(|val| val * val).collect();
+        let norm = self.config.quadrature.integrate(&density, self.config.step_size).sqrt();
+
+        psi.iter()
+            .enumerate()
+            .map(|(i, val)| (self.x_from_index(i), val / norm))
+            .collect()
+    }
+
+    fn energy_lower_bound(&self) -> f64 {
+        (0..self.steps)
+            .map(|i| self.config.potential.value(self.x_from_index(i)))
+            .fold(f64::INFINITY, f64::min)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::physics::harmonic_potential;
+    use crate::physics::solvers::variational_monte_carlo::*;
+
+    #[test]
+    fn harmonic_oscillator_energy() {
+        let alpha = 0.4;
+        let config = VariationalMonteCarloConfig {
+            x_min: -5.0,
+            x_max: 5.0,
+            step_size: 0.01,
+            potential: harmonic_potential,
+            trial_wavefunction: move |x: f64| (-alpha * x * x).exp(),
+            initial_position: 0.0,
+            move_size: 1.5,
+            thermalization_steps: 1000,
+            samples: 200000,
+            block_size: 1000,
+            seed: 42,
+            quadrature: Quadrature::Trapezoidal,
+        };
+        let mut solver = VariationalMonteCarloSolver::new(&config);
+        solver.solve().unwrap();
+
+        // <H> = α/2 + 1/8α for a Gaussian trial wavefunction
+        let exact = 0.5 * alpha + 0.125 / alpha;
+        assert!((solver.energy() - exact).abs() < 4.0 * solver.error() + 1e-4);
+        assert!(solver.error() > 0.0 && solver.error() < 5e-3);
+        assert!(solver.acceptance_ratio() > 0.2 && solver.acceptance_ratio() < 0.9);
+
+        // The same seed replays the same walk
+        let energy = solver.energy();
+        solver.reset();
+        solver.solve().unwrap();
+        assert_eq!(solver.energy(), energy);
+
+        // The observer sees whether every proposed move was accepted
+        let mut accepted = 0;
+        solver.reset();
+        solver
+            .solve_observed(&mut |progress: &Progress| {
+                if progress.accepted.unwrap() {
+                    accepted += 1;
+                }
+                Control::Continue
+            })
+            .unwrap();
+        assert_eq!(accepted as f64 / 201000.0, solver.acceptance_ratio());
+
+        // The exact ground state has zero variance
+        let mut solver = VariationalMonteCarloSolver::new(&VariationalMonteCarloConfig {
+            x_min: -5.0,
+            x_max: 5.0,
+            step_size: 0.01,
+            potential: harmonic_potential,
+            trial_wavefunction: |x: f64| (-0.5 * x * x).exp(),
+            initial_position: 0.0,
+            move_size: 1.5,
+            thermalization_steps: 1000,
+            samples: 10000,
+            block_size: 1000,
+            seed: 42,
+            quadrature: Quadrature::Trapezoidal,
+        });
+        solver.solve().unwrap();
+        assert!((solver.energy() - 0.5).abs() < 1e-6);
+    }
+}
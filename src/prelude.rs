@@ -3,13 +3,38 @@ pub use crate::physics::{
     double_well_potential,
     harmonic_potential,
     lennard_jones_potential,
+    potentials::{
+        DoubleWell, HarmonicOscillator, LennardJones, Potential, SquareWell,
+    },
+    scattering::{ScatteringCoefficients, ScatteringConfig, ScatteringSolver},
+    observables::Observables,
+    momentum_space::{momentum_density, momentum_wavefunction},
+    phase_space::{husimi, wigner},
+    analytic::{
+        ExactSolution, HydrogenRadial, InfiniteSquareWell, Morse, PoschlTeller, TriangularWell,
+    },
+    dynamics::{
+        gaussian_wavepacket,
+        split_operator::{SplitOperatorConfig, SplitOperatorPropagator},
+        crank_nicolson::{AbsorbingBoundary, CrankNicolsonConfig, CrankNicolsonPropagator},
+        Snapshot,
+    },
 };
 
 pub use crate::physics::solvers::{
     shooting::{ShootingConfig, ShootingSolver, Parity},
     matching::{MatchingConfig, MatchingSolver},
-    variational::{VariationalSolver, VariationalConfig},
+    variational::{StepSchedule, VariationalSolver, VariationalConfig},
+    diagonalization::{DiagonalizationConfig, DiagonalizationSolver},
+    radial::{RadialConfig, RadialSolver},
+    parametric::{Optimizer, ParametricConfig, ParametricSolver},
+    variational_monte_carlo::{VariationalMonteCarloConfig, VariationalMonteCarloSolver},
+    diffusion_monte_carlo::{DiffusionMonteCarloConfig, DiffusionMonteCarloSolver},
+    imaginary_time::{ImaginaryTimeConfig, ImaginaryTimeSolver},
+    observer::{Control, Observer, Progress, Silent},
+    solution::{Convergence, Solution},
     Solver,
 };
 
 pub use crate::utils::*;
+pub use crate::utils::integration::Quadrature;
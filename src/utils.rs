@@ -1,6 +1,11 @@
 pub mod integration;
 pub mod finite_difference;
 pub mod root_finding;
+pub mod fourier;
+pub mod tridiagonal;
+pub mod optimization;
+pub mod statistics;
+pub mod special;
 
 
 pub fn gen_range(range: std::ops::RangeInclusive<f64>, step: f64) -> Vec<f64> {
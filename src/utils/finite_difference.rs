@@ -4,6 +4,20 @@ pub enum SecondDerivateMethod {
     BackwardDifference,
 }
 
+impl SecondDerivateMethod {
+    /// Returns the method to use at index `i` of a grid with `steps` points: one
+    /// sided differences at the ends and central differences elsewhere.
+    pub fn at(i: usize, steps: usize) -> Self {
+        if i == 0 {
+            SecondDerivateMethod::ForwardDifference
+        } else if i == steps - 1 {
+            SecondDerivateMethod::BackwardDifference
+        } else {
+            SecondDerivateMethod::CentralDifference
+        }
+    }
+}
+
 pub fn second_derivative(
     method: &SecondDerivateMethod,
     f: &[f64],
@@ -23,6 +37,53 @@ pub fn second_derivative(
     }
 }
 
+/// Approximates f'(x_i) to second order. The forward and backward differences
+/// only use points on one side of x_i, for use at the ends of the grid.
+pub fn first_derivative(
+    method: &SecondDerivateMethod,
+    f: &[f64],
+    i: usize,
+    step_size: f64,
+) -> f64 {
+    match method {
+        SecondDerivateMethod::ForwardDifference => {
+            (-3.0 * f[i] + 4.0 * f[i + 1] - f[i + 2]) / (2.0 * step_size)
+        }
+        SecondDerivateMethod::CentralDifference => (f[i + 1] - f[i - 1]) / (2.0 * step_size),
+        SecondDerivateMethod::BackwardDifference => {
+            (3.0 * f[i] - 4.0 * f[i - 1] + f[i - 2]) / (2.0 * step_size)
+        }
+    }
+}
+
+/// Takes one step of Numerov's method for ψ'' = -k²(x)ψ, returning ψ at the next
+/// grid point from its values at the last two points. `k_sqr_next`, `k_sqr_last` and
+/// `k_sqr_second_to_last` are k² at the next, last and second to last points.
+pub fn numerov_step(
+    psi_last: f64,
+    psi_second_to_last: f64,
+    k_sqr_next: f64,
+    k_sqr_last: f64,
+    k_sqr_second_to_last: f64,
+    step_size: f64,
+) -> f64 {
+    let h_sqr = step_size * step_size;
+    (2.0 * (1.0 - (5.0 / 12.0) * h_sqr * k_sqr_last) * psi_last
+        - (1.0 + (1.0 / 12.0) * h_sqr * k_sqr_second_to_last) * psi_second_to_last)
+        / (1.0 + (1.0 / 12.0) * h_sqr * k_sqr_next)
+}
+
+/// Takes one step of the central second difference approximation to ψ'' = -k²(x)ψ,
+/// returning ψ at the next grid point from its values at the last two points.
+pub fn second_difference_step(
+    psi_last: f64,
+    psi_second_to_last: f64,
+    k_sqr_last: f64,
+    step_size: f64,
+) -> f64 {
+    (2.0 - step_size * step_size * k_sqr_last) * psi_last - psi_second_to_last
+}
+
 #[cfg(test)]
 mod tests {
     use crate::utils::finite_difference::*;
@@ -61,4 +122,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn first_derivative_test() {
+        let f: Vec<f64> = gen_range(0.0..=1.0, 0.01)
+            .iter_mut()
+            .map(|x| *x * *x)
+            .collect();
+        let methods = [
+            (SecondDerivateMethod::ForwardDifference, 0),
+            (SecondDerivateMethod::CentralDifference, 50),
+            (SecondDerivateMethod::BackwardDifference, 99),
+        ];
+        for (method, i) in methods.iter() {
+            let expected = 2.0 * (*i as f64) * 0.01;
+            assert!((first_derivative(method, &f, *i, 0.01) - expected).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn stepping() {
+        // ψ = cos(kx) solves ψ'' = -k²ψ
+        let k: f64 = 3.0;
+        let step = 0.01;
+        let mut numerov = vec![1.0, (k * step).cos()];
+        let mut second_difference = numerov.clone();
+        for i in 2..=100 {
+            numerov.push(numerov_step(numerov[i - 1], numerov[i - 2], k * k, k * k, k * k, step));
+            second_difference.push(second_difference_step(
+                second_difference[i - 1],
+                second_difference[i - 2],
+                k * k,
+                step,
+            ));
+        }
+
+        let expected = (k * 100.0 * step).cos();
+        assert!((numerov[100] - expected).abs() < 1e-8);
+        assert!((second_difference[100] - expected).abs() < 1e-3);
+    }
 }
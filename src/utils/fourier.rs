@@ -0,0 +1,157 @@
+use std::f64::consts::PI;
+
+use num_complex::Complex64;
+
+/// Computes the discrete Fourier transform Φₖ = Σⱼ ψⱼ exp(-2πijk/n). Power of two
+/// lengths use the radix-2 Cooley-Tukey algorithm, and other lengths are reduced
+/// to a power of two with Bluestein's algorithm.
+pub fn fft(data: &[Complex64]) -> Vec<Complex64> {
+    transform(data, -1.0)
+}
+
+/// Computes the inverse of `fft`, ψⱼ = (1/n) Σₖ Φₖ exp(2πijk/n).
+pub fn ifft(data: &[Complex64]) -> Vec<Complex64> {
+    let n = data.len() as f64;
+    transform(data, 1.0).into_iter().map(|val| val / n).collect()
+}
+
+/// Returns the angular wavenumbers matching the output of `fft` for samples spaced
+/// by `step_size`, in the same order (zero, then positive, then negative).
+pub fn fft_wavenumbers(n: usize, step_size: f64) -> Vec<f64> {
+    let dk = 2.0 * PI / (n as f64 * step_size);
+    (0..n)
+        .map(|j| {
+            if j < n.div_ceil(2) {
+                j as f64 * dk
+            } else {
+                (j as f64 - n as f64) * dk
+            }
+        })
+        .collect()
+}
+
+/// Unnormalized transform with exp(sign * 2πijk/n).
+fn transform(data: &[Complex64], sign: f64) -> Vec<Complex64> {
+    let n = data.len();
+    if n <= 1 {
+        return data.to_vec();
+    }
+
+    if n.is_power_of_two() {
+        let mut result = data.to_vec();
+        radix_2(&mut result, sign);
+        result
+    } else {
+        bluestein(data, sign)
+    }
+}
+
+/// In place iterative radix-2 transform. The length must be a power of two.
+fn radix_2(data: &mut [Complex64], sign: f64) {
+    let n = data.len();
+
+    // Bit reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+
+    let mut length = 2;
+    while length <= n {
+        let root = Complex64::from_polar(1.0, sign * 2.0 * PI / length as f64);
+        for start in (0..n).step_by(length) {
+            let mut twiddle = Complex64::new(1.0, 0.0);
+            for k in 0..length / 2 {
+                let even = data[start + k];
+                let odd = data[start + k + length / 2] * twiddle;
+                data[start + k] = even + odd;
+                data[start + k + length / 2] = even - odd;
+                twiddle *= root;
+            }
+        }
+        length <<= 1;
+    }
+}
+
+/// Bluestein's algorithm, which writes a transform of any length as a convolution
+/// that is evaluated with power of two transforms.
+fn bluestein(data: &[Complex64], sign: f64) -> Vec<Complex64> {
+    let n = data.len();
+    let m = (2 * n - 1).next_power_of_two();
+
+    // The chirp exp(sign * πik²/n), with k² reduced modulo 2n to keep the phase accurate
+    let chirp: Vec<Complex64> = (0..n)
+        .map(|k| {
+            let k_sqr = (k * k) % (2 * n);
+            Complex64::from_polar(1.0, sign * PI * k_sqr as f64 / n as f64)
+        })
+        .collect();
+
+    let mut a = vec![Complex64::new(0.0, 0.0); m];
+    for (a, (val, c)) in a.iter_mut().zip(data.iter().zip(chirp.iter())) {
+        *a = val * c;
+    }
+
+    let mut b = vec![Complex64::new(0.0, 0.0); m];
+    b[0] = chirp[0].conj();
+    for k in 1..n {
+        b[k] = chirp[k].conj();
+        b[m - k] = chirp[k].conj();
+    }
+
+    radix_2(&mut a, -1.0);
+    radix_2(&mut b, -1.0);
+    let mut convolution: Vec<Complex64> = a.iter().zip(b.iter()).map(|(a, b)| a * b).collect();
+    radix_2(&mut convolution, 1.0);
+
+    (0..n)
+        .map(|k| convolution[k] / m as f64 * chirp[k])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::fourier::*;
+
+    fn naive_dft(data: &[Complex64]) -> Vec<Complex64> {
+        let n = data.len();
+        (0..n)
+            .map(|k| {
+                data.iter()
+                    .enumerate()
+                    .map(|(j, val)| {
+                        val * Complex64::from_polar(1.0, -2.0 * PI * (j * k) as f64 / n as f64)
+                    })
+                    .sum()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matches_naive_dft() {
+        for n in [1, 2, 8, 12, 31, 64] {
+            let data: Vec<Complex64> = (0..n)
+                .map(|j| Complex64::new((j as f64).sin(), (0.3 * j as f64).cos()))
+                .collect();
+
+            let expected = naive_dft(&data);
+            let transformed = fft(&data);
+            for (a, b) in transformed.iter().zip(expected.iter()) {
+                assert!((a - b).norm() < 1e-9);
+            }
+
+            let restored = ifft(&transformed);
+            for (a, b) in restored.iter().zip(data.iter()) {
+                assert!((a - b).norm() < 1e-12);
+            }
+        }
+    }
+}
@@ -1,3 +1,32 @@
+use std::f64::consts::PI;
+use std::ops::RangeInclusive;
+
+use crate::error::Error;
+
+/// A rule for integrating values sampled on a uniform grid, as used by the
+/// solvers to normalize wavefunctions and evaluate expectation values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Quadrature {
+    /// The trapezoidal rule, with an error of O(h²).
+    #[default]
+    Trapezoidal,
+    /// Simpson's rule, with an error of O(h⁴).
+    Simpson,
+    /// Boole's rule, with an error of O(h⁶).
+    Boole,
+}
+
+impl Quadrature {
+    /// Returns the integral of the values `f` spaced by `delta_x`.
+    pub fn integrate(&self, f: &[f64], delta_x: f64) -> f64 {
+        match self {
+            Quadrature::Trapezoidal => trapezoidal(f, &delta_x),
+            Quadrature::Simpson => simpson(f, delta_x),
+            Quadrature::Boole => boole(f, delta_x),
+        }
+    }
+}
+
 pub fn trapezoidal(f: &[f64], delta_x: &f64) -> f64 {
     let mut sum = 0.0;
     for window in f.windows(2) {
@@ -6,9 +35,262 @@ pub fn trapezoidal(f: &[f64], delta_x: &f64) -> f64 {
     0.5 * delta_x * sum
 }
 
+/// Integrates (x, f(x)) points with the trapezoidal rule. The points must be in
+/// increasing order of x but do not need to be evenly spaced.
+pub fn trapezoidal_points(points: &[(f64, f64)]) -> f64 {
+    points
+        .windows(2)
+        .map(|w| 0.5 * (w[1].0 - w[0].0) * (w[0].1 + w[1].1))
+        .sum()
+}
+
+/// Integrates the values `f` spaced by `delta_x` with the composite Simpson's
+/// rule. If there is an odd number of intervals, the last three are integrated
+/// with Simpson's 3/8 rule instead. Two points fall back to the trapezoidal rule.
+pub fn simpson(f: &[f64], delta_x: f64) -> f64 {
+    let intervals = f.len().saturating_sub(1);
+    if intervals < 2 {
+        return trapezoidal(f, &delta_x);
+    }
+
+    let (even, rest) = if intervals.is_multiple_of(2) {
+        (intervals, 0.0)
+    } else {
+        let tail = &f[intervals - 3..];
+        (
+            intervals - 3,
+            0.375 * delta_x * (tail[0] + 3.0 * tail[1] + 3.0 * tail[2] + tail[3]),
+        )
+    };
+
+    let sum: f64 = f[..=even]
+        .windows(3)
+        .step_by(2)
+        .map(|w| w[0] + 4.0 * w[1] + w[2])
+        .sum();
+    delta_x * sum / 3.0 + rest
+}
+
+/// Integrates the values `f` spaced by `delta_x` with the composite Boole's rule.
+/// Intervals left over at the end are integrated with the polynomial through the
+/// last five points, which has the same order. Fewer than five points fall back to
+/// `simpson`.
+pub fn boole(f: &[f64], delta_x: f64) -> f64 {
+    let intervals = f.len().saturating_sub(1);
+    if intervals < 4 {
+        return simpson(f, delta_x);
+    }
+
+    let whole = intervals - intervals % 4;
+    let sum: f64 = f[..=whole]
+        .windows(5)
+        .step_by(4)
+        .map(|w| 7.0 * (w[0] + w[4]) + 32.0 * (w[1] + w[3]) + 12.0 * w[2])
+        .sum();
+
+    // Weights (times 720) of the last five points for the last 1, 2 or 3 intervals
+    let weights: [f64; 5] = match intervals % 4 {
+        1 => [-19.0, 106.0, -264.0, 646.0, 251.0],
+        2 => [-8.0, 32.0, 192.0, 992.0, 232.0],
+        3 => [-27.0, 378.0, 648.0, 918.0, 243.0],
+        _ => [0.0; 5],
+    };
+    let rest: f64 = f[intervals - 4..]
+        .iter()
+        .zip(weights.iter())
+        .map(|(val, weight)| val * weight)
+        .sum();
+
+    2.0 * delta_x * sum / 45.0 + delta_x * rest / 720.0
+}
+
+/// Integrates `f` over `range` with Romberg's method, halving the step of the
+/// trapezoidal rule up to `max_levels` times and extrapolating to zero step size.
+/// Fails if two successive extrapolations do not agree to within `tolerance`.
+pub fn romberg(
+    f: impl Fn(f64) -> f64,
+    range: RangeInclusive<f64>,
+    tolerance: f64,
+    max_levels: usize,
+) -> Result<f64, Error> {
+    let (a, b) = (*range.start(), *range.end());
+    let mut h = b - a;
+    let mut previous = vec![0.5 * h * (f(a) + f(b))];
+
+    for level in 1..=max_levels {
+        h *= 0.5;
+        let points = 1 << (level - 1);
+        let midpoints: f64 = (0..points).map(|i| f(a + (2 * i + 1) as f64 * h)).sum();
+
+        let mut current = Vec::with_capacity(level + 1);
+        current.push(0.5 * previous[0] + h * midpoints);
+        for k in 1..=level {
+            let factor = 4.0_f64.powi(k as i32);
+            current.push(current[k - 1] + (current[k - 1] - previous[k - 1]) / (factor - 1.0));
+        }
+
+        if (current[level] - previous[level - 1]).abs() <= tolerance {
+            return Ok(current[level]);
+        }
+        previous = current;
+    }
+
+    Err(Error::NonConvergence {
+        iterations: max_levels,
+    })
+}
+
+/// Returns the (x, weight) pairs of the Gauss-Legendre rule with `order` nodes on
+/// [-1, 1], which is exact for polynomials of degree up to 2 * order - 1.
+pub fn gauss_legendre_nodes(order: usize) -> Vec<(f64, f64)> {
+    let mut nodes = vec![(0.0, 0.0); order];
+
+    // The nodes are symmetric about zero, so only half of them are searched for
+    for i in 0..order.div_ceil(2) {
+        let mut x = (PI * (i as f64 + 0.75) / (order as f64 + 0.5)).cos();
+        let mut derivative = 0.0;
+        for _ in 0..100 {
+            // P_order(x) and P_order-1(x) by recurrence
+            let (mut previous, mut current) = (1.0, x);
+            for k in 2..=order {
+                let k = k as f64;
+                let next = ((2.0 * k - 1.0) * x * current - (k - 1.0) * previous) / k;
+                previous = current;
+                current = next;
+            }
+            derivative = order as f64 * (x * current - previous) / (x * x - 1.0);
+            let step = current / derivative;
+            x -= step;
+            if step.abs() <= f64::EPSILON {
+                break;
+            }
+        }
+
+        let weight = 2.0 / ((1.0 - x * x) * derivative * derivative);
+        nodes[i] = (-x, weight);
+        nodes[order - 1 - i] = (x, weight);
+    }
+    nodes
+}
+
+/// Integrates `f` over `range` with the Gauss-Legendre rule with `order` nodes.
+pub fn gauss_legendre(f: impl Fn(f64) -> f64, range: RangeInclusive<f64>, order: usize) -> f64 {
+    let half_width = 0.5 * (range.end() - range.start());
+    let center = 0.5 * (range.end() + range.start());
+    half_width
+        * gauss_legendre_nodes(order)
+            .iter()
+            .map(|(x, weight)| weight * f(center + half_width * x))
+            .sum::<f64>()
+}
+
+/// Returns the (x, weight) pairs of the Gauss-Hermite rule with `order` nodes for
+/// the weight function exp(-x²) on the whole real line.
+pub fn gauss_hermite_nodes(order: usize) -> Vec<(f64, f64)> {
+    let mut nodes = vec![(0.0, 0.0); order];
+    let n = order as f64;
+
+    // Initial guesses for the largest nodes, then extrapolation from the previous
+    // two, as in Numerical Recipes
+    for i in 0..order.div_ceil(2) {
+        let mut x = match i {
+            0 => (2.0 * n + 1.0).sqrt() - 1.85575 * (2.0 * n + 1.0).powf(-1.0 / 6.0),
+            1 => nodes[0].0 - 1.14 * n.powf(0.426) / nodes[0].0,
+            2 => 1.86 * nodes[1].0 - 0.86 * nodes[0].0,
+            3 => 1.91 * nodes[2].0 - 0.91 * nodes[1].0,
+            _ => 2.0 * nodes[i - 1].0 - nodes[i - 2].0,
+        };
+
+        let mut derivative = 0.0;
+        for _ in 0..100 {
+            // The orthonormal Hermite functions by recurrence
+            let (mut previous, mut current) = (0.0, PI.powf(-0.25));
+            for k in 1..=order {
+                let k = k as f64;
+                let next = x * (2.0 / k).sqrt() * current - ((k - 1.0) / k).sqrt() * previous;
+                previous = current;
+                current = next;
+            }
+            derivative = (2.0 * n).sqrt() * previous;
+            let step = current / derivative;
+            x -= step;
+            if step.abs() <= 4.0 * f64::EPSILON * x.abs().max(1.0) {
+                break;
+            }
+        }
+
+        let weight = 2.0 / (derivative * derivative);
+        nodes[i] = (x, weight);
+        nodes[order - 1 - i] = (-x, weight);
+    }
+
+    nodes.reverse();
+    nodes
+}
+
+/// Returns ∫exp(-x²)f(x)dx over the whole real line with the Gauss-Hermite rule
+/// with `order` nodes.
+pub fn gauss_hermite(f: impl Fn(f64) -> f64, order: usize) -> f64 {
+    gauss_hermite_nodes(order)
+        .iter()
+        .map(|(x, weight)| weight * f(*x))
+        .sum()
+}
+
+/// Integrates `f` over `range` with adaptive Simpson's rule, bisecting intervals
+/// until the estimated error is below `tolerance`. Fails if an interval still has
+/// not converged after `max_depth` bisections.
+pub fn adaptive_simpson(
+    f: impl Fn(f64) -> f64,
+    range: RangeInclusive<f64>,
+    tolerance: f64,
+    max_depth: usize,
+) -> Result<f64, Error> {
+    let (a, b) = (*range.start(), *range.end());
+    let (fa, fm, fb) = (f(a), f(0.5 * (a + b)), f(b));
+    let whole = (b - a) * (fa + 4.0 * fm + fb) / 6.0;
+    adaptive_simpson_step(&f, (a, fa), (b, fb), fm, whole, tolerance, max_depth).ok_or(
+        Error::NonConvergence {
+            iterations: max_depth,
+        },
+    )
+}
+
+/// Refines the Simpson's rule estimate `whole` of the integral from a to b, given
+/// the values at the ends and the midpoint.
+fn adaptive_simpson_step(
+    f: &impl Fn(f64) -> f64,
+    (a, fa): (f64, f64),
+    (b, fb): (f64, f64),
+    fm: f64,
+    whole: f64,
+    tolerance: f64,
+    depth: usize,
+) -> Option<f64> {
+    let m = 0.5 * (a + b);
+    let (left_m, right_m) = (0.5 * (a + m), 0.5 * (m + b));
+    let (f_left, f_right) = (f(left_m), f(right_m));
+    let left = (m - a) * (fa + 4.0 * f_left + fm) / 6.0;
+    let right = (b - m) * (fm + 4.0 * f_right + fb) / 6.0;
+    let error = left + right - whole;
+
+    if error.abs() <= 15.0 * tolerance {
+        // Richardson extrapolation
+        return Some(left + right + error / 15.0);
+    }
+    if depth == 0 {
+        return None;
+    }
+
+    let tolerance = 0.5 * tolerance;
+    let left = adaptive_simpson_step(f, (a, fa), (m, fm), f_left, left, tolerance, depth - 1)?;
+    let right = adaptive_simpson_step(f, (m, fm), (b, fb), f_right, right, tolerance, depth - 1)?;
+    Some(left + right)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::utils::integration::trapezoidal;
+    use crate::utils::integration::*;
     use crate::utils::gen_range;
 
     #[test]
@@ -20,4 +302,59 @@ mod tests {
             .collect();
         assert!((0.31..0.35).contains(&trapezoidal(&f, &step)));
     }
+
+    #[test]
+    fn higher_order_rules() {
+        // ∫₀^π sin x dx = 2, with every remainder of intervals modulo four
+        for intervals in 40..44 {
+            let step = PI / intervals as f64;
+            let f: Vec<f64> = (0..=intervals).map(|i| (i as f64 * step).sin()).collect();
+            let trapezoidal_error = (Quadrature::Trapezoidal.integrate(&f, step) - 2.0).abs();
+            let simpson_error = (Quadrature::Simpson.integrate(&f, step) - 2.0).abs();
+            let boole_error = (Quadrature::Boole.integrate(&f, step) - 2.0).abs();
+            assert!(trapezoidal_error < 2e-3);
+            assert!(simpson_error < 2e-6);
+            assert!(boole_error < 1e-8);
+        }
+
+        // Exact for low degree polynomials
+        let cubic = |x: f64| 4.0 * x * x * x - x + 1.0;
+        let f: Vec<f64> = (0..=5).map(|i| cubic(0.2 * i as f64)).collect();
+        assert!((simpson(&f, 0.2) - 1.5).abs() < 1e-14);
+        assert!((boole(&f, 0.2) - 1.5).abs() < 1e-14);
+
+        let points: Vec<(f64, f64)> = (0..=1000)
+            .map(|i| {
+                let x = (i as f64 / 1000.0).powi(2);
+                (x, x.exp())
+            })
+            .collect();
+        assert!((trapezoidal_points(&points) - (1.0_f64.exp() - 1.0)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn closure_rules() {
+        let exact = 1.0_f64.exp() - (-1.0_f64).exp();
+        assert!((romberg(f64::exp, -1.0..=1.0, 1e-12, 20).unwrap() - exact).abs() < 1e-12);
+        assert!((adaptive_simpson(f64::exp, -1.0..=1.0, 1e-12, 50).unwrap() - exact).abs() < 1e-11);
+        assert!((gauss_legendre(f64::exp, -1.0..=1.0, 10) - exact).abs() < 1e-14);
+        assert!(matches!(
+            romberg(|x: f64| x.abs().sqrt(), -1.0..=1.0, 1e-15, 3),
+            Err(Error::NonConvergence { iterations: 3 })
+        ));
+
+        for order in [1, 2, 5, 20, 64] {
+            let nodes = gauss_legendre_nodes(order);
+            assert!(nodes.windows(2).all(|w| w[0].0 < w[1].0));
+            assert!((nodes.iter().map(|(_, w)| w).sum::<f64>() - 2.0).abs() < 1e-13);
+
+            let nodes = gauss_hermite_nodes(order);
+            assert!(nodes.windows(2).all(|w| w[0].0 < w[1].0));
+            assert!((nodes.iter().map(|(_, w)| w).sum::<f64>() - PI.sqrt()).abs() < 1e-12);
+        }
+
+        // ∫exp(-x²)x⁴dx = 3√π/4 and ∫exp(-x²)cos x dx = √π exp(-1/4)
+        assert!((gauss_hermite(|x| x.powi(4), 3) - 0.75 * PI.sqrt()).abs() < 1e-13);
+        assert!((gauss_hermite(f64::cos, 20) - PI.sqrt() * (-0.25_f64).exp()).abs() < 1e-13);
+    }
 }
@@ -0,0 +1,213 @@
+use crate::error::Error;
+
+/// The result of a minimization.
+#[derive(Clone, Debug)]
+pub struct Minimum {
+    /// The parameters at the minimum.
+    pub point: Vec<f64>,
+    /// The function value at the minimum.
+    pub value: f64,
+    /// The lowest function value found after each iteration.
+    pub history: Vec<f64>,
+}
+
+/// Minimizes `f` with the Nelder-Mead simplex method, starting from a simplex
+/// around `initial` with edges of length `initial_step` along each axis. Stops
+/// once the function values on the simplex differ by less than `tolerance`.
+///
+/// `observe` is called with the iteration and the lowest value found after each
+/// iteration. Returning `false` stops the minimization with `Error::Cancelled`.
+/// Returns `Error::InvalidConfig` if `initial` is empty.
+pub fn nelder_mead(
+    f: impl Fn(&[f64]) -> f64,
+    initial: &[f64],
+    initial_step: f64,
+    tolerance: f64,
+    max_iterations: usize,
+    mut observe: impl FnMut(usize, f64) -> bool,
+) -> Result<Minimum, Error> {
+    let dimension = initial.len();
+    if dimension == 0 {
+        return Err(Error::InvalidConfig(
+            "initial parameters must not be empty".to_string(),
+        ));
+    }
+
+    let mut simplex: Vec<(Vec<f64>, f64)> = Vec::with_capacity(dimension + 1);
+    simplex.push((initial.to_vec(), f(initial)));
+    for i in 0..dimension {
+        let mut vertex = initial.to_vec();
+        vertex[i] += initial_step;
+        let value = f(&vertex);
+        simplex.push((vertex, value));
+    }
+
+    let mut history = Vec::new();
+    for iteration in 0..max_iterations {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        history.push(simplex[0].1);
+
+        if !simplex[0].1.is_finite() {
+            return Err(Error::NumericalOverflow);
+        }
+        if !observe(iteration, simplex[0].1) {
+            return Err(Error::Cancelled {
+                iterations: iteration + 1,
+            });
+        }
+        if simplex[dimension].1 - simplex[0].1 < tolerance {
+            return Ok(Minimum {
+                point: simplex[0].0.clone(),
+                value: simplex[0].1,
+                history,
+            });
+        }
+
+        // Centroid of every vertex but the worst
+        let mut centroid = vec![0.0; dimension];
+        for (vertex, _) in simplex.iter().take(dimension) {
+            for (c, x) in centroid.iter_mut().zip(vertex.iter()) {
+                *c += x / dimension as f64;
+            }
+        }
+        let toward_worst = |t: f64| -> Vec<f64> {
+            centroid
+                .iter()
+                .zip(simplex[dimension].0.iter())
+                .map(|(c, worst)| c + t * (worst - c))
+                .collect()
+        };
+
+        let reflected = toward_worst(-1.0);
+        let reflected_value = f(&reflected);
+
+        if reflected_value < simplex[0].1 {
+            let expanded = toward_worst(-2.0);
+            let expanded_value = f(&expanded);
+            simplex[dimension] = if expanded_value < reflected_value {
+                (expanded, expanded_value)
+            } else {
+                (reflected, reflected_value)
+            };
+        } else if reflected_value < simplex[dimension - 1].1 {
+            simplex[dimension] = (reflected, reflected_value);
+        } else {
+            let contracted = if reflected_value < simplex[dimension].1 {
+                toward_worst(-0.5)
+            } else {
+                toward_worst(0.5)
+            };
+            let contracted_value = f(&contracted);
+
+            if contracted_value < reflected_value.min(simplex[dimension].1) {
+                simplex[dimension] = (contracted, contracted_value);
+            } else {
+                // Shrink every vertex toward the best one
+                let best = simplex[0].0.clone();
+                for (vertex, value) in simplex.iter_mut().skip(1) {
+                    for (x, b) in vertex.iter_mut().zip(best.iter()) {
+                        *x = b + 0.5 * (*x - b);
+                    }
+                    *value = f(vertex);
+                }
+            }
+        }
+    }
+
+    Err(Error::NonConvergence {
+        iterations: max_iterations,
+    })
+}
+
+/// Minimizes `f` by gradient descent with a fixed learning rate, estimating the
+/// gradient with central differences. Stops once a step changes the function
+/// value by less than `tolerance`. `observe` is called as in `nelder_mead`.
+pub fn gradient_descent(
+    f: impl Fn(&[f64]) -> f64,
+    initial: &[f64],
+    learning_rate: f64,
+    tolerance: f64,
+    max_iterations: usize,
+    mut observe: impl FnMut(usize, f64) -> bool,
+) -> Result<Minimum, Error> {
+    let mut point = initial.to_vec();
+    let mut value = f(&point);
+    let mut history = Vec::new();
+
+    for iteration in 0..max_iterations {
+        let gradient: Vec<f64> = (0..point.len())
+            .map(|i| {
+                let delta = 1e-6 * point[i].abs().max(1.0);
+                let mut forward = point.clone();
+                let mut backward = point.clone();
+                forward[i] += delta;
+                backward[i] -= delta;
+                (f(&forward) - f(&backward)) / (2.0 * delta)
+            })
+            .collect();
+
+        for (x, g) in point.iter_mut().zip(gradient.iter()) {
+            *x -= learning_rate * g;
+        }
+
+        let last_value = value;
+        value = f(&point);
+        history.push(value);
+
+        if !value.is_finite() {
+            return Err(Error::NumericalOverflow);
+        }
+        if !observe(iteration, value) {
+            return Err(Error::Cancelled {
+                iterations: iteration + 1,
+            });
+        }
+        if (last_value - value).abs() < tolerance {
+            return Ok(Minimum {
+                point,
+                value,
+                history,
+            });
+        }
+    }
+
+    Err(Error::NonConvergence {
+        iterations: max_iterations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::optimization::*;
+
+    fn rosenbrock(p: &[f64]) -> f64 {
+        (1.0 - p[0]).powi(2) + 100.0 * (p[1] - p[0] * p[0]).powi(2)
+    }
+
+    #[test]
+    fn minimizes() {
+        let minimum =
+            nelder_mead(rosenbrock, &[-1.2, 1.0], 0.5, 1e-14, 10000, |_, _| true).unwrap();
+        assert!((minimum.point[0] - 1.0).abs() < 1e-4);
+        assert!((minimum.point[1] - 1.0).abs() < 1e-4);
+        assert!(minimum.history.windows(2).all(|w| w[1] <= w[0]));
+
+        let bowl = |p: &[f64]| (p[0] - 2.0).powi(2) + 3.0 * (p[1] + 1.0).powi(2);
+        let minimum = gradient_descent(bowl, &[0.0, 0.0], 0.1, 1e-14, 10000, |_, _| true).unwrap();
+        assert!((minimum.point[0] - 2.0).abs() < 1e-5);
+        assert!((minimum.point[1] + 1.0).abs() < 1e-5);
+
+        assert!(matches!(
+            nelder_mead(rosenbrock, &[-1.2, 1.0], 0.5, 1e-14, 5, |_, _| true),
+            Err(Error::NonConvergence { iterations: 5 })
+        ));
+        assert!(matches!(
+            nelder_mead(rosenbrock, &[-1.2, 1.0], 0.5, 1e-14, 10000, |i, _| i < 2),
+            Err(Error::Cancelled { iterations: 3 })
+        ));
+        assert!(matches!(
+            nelder_mead(|_| 0.0, &[], 0.5, 0.0, 10, |_, _| true),
+            Err(Error::InvalidConfig(_))
+        ));
+    }
+}
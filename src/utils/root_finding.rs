@@ -1,9 +1,14 @@
+use crate::error::Error;
+
+/// Finds a root of `f` with the secant method, starting from the ends of `range`.
+/// Fails if the relative change does not drop below `tolerance` within `max_loops`
+/// iterations.
 pub fn find_root(
     f: impl Fn(f64) -> f64,
     range: std::ops::RangeInclusive<f64>,
     tolerance: f64,
     max_loops: usize,
-) -> Option<f64> {
+) -> Result<f64, Error> {
     let mut x0 = *range.start();
     let mut x1 = *range.end();
     for _ in 1..=max_loops {
@@ -11,17 +16,23 @@ pub fn find_root(
         let fx1 = f(x1);
 
         let x2 = x1 - fx1 * (x1 - x0) / (fx1 - fx0);
+        if !x2.is_finite() {
+            return Err(Error::NumericalOverflow);
+        }
+
         let interval = (x2 - x1).abs() / x1.abs();
 
         if interval < tolerance {
-            return Some(x2);
+            return Ok(x2);
         }
 
         x0 = x1;
         x1 = x2;
     }
 
-    None
+    Err(Error::NonConvergence {
+        iterations: max_loops,
+    })
 }
 
 #[cfg(test)]
@@ -35,7 +46,7 @@ mod tests {
             1e-15,
             500
         );
-        assert!(root.is_some());
+        assert!(root.is_ok());
         assert!((root.unwrap() - 3.14).abs() < 0.01);
     }
 }
@@ -0,0 +1,354 @@
+//! Special functions for analytic reference solutions. The orthogonal polynomials
+//! are evaluated with their three-term recurrences, which are stable in the
+//! upward direction.
+
+use std::f64::consts::PI;
+
+/// Returns Γ(x), using the Lanczos approximation and the reflection formula for
+/// x < ½. Γ has poles at zero and the negative integers.
+pub fn gamma(x: f64) -> f64 {
+    if x < 0.5 {
+        PI / ((PI * x).sin() * gamma(1.0 - x))
+    } else {
+        ln_gamma(x).exp()
+    }
+}
+
+/// Returns ln Γ(x) for x > 0, which stays finite long after Γ(x) overflows.
+pub fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+
+    if x < 0.5 {
+        // Reflection formula
+        return (PI / (PI * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let sum = COEFFICIENTS
+        .iter()
+        .enumerate()
+        .skip(1)
+        .fold(COEFFICIENTS[0], |sum, (i, c)| sum + c / (x + i as f64));
+    0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// Returns the physicists' Hermite polynomial Hₙ(x).
+pub fn hermite(n: usize, x: f64) -> f64 {
+    let (mut previous, mut current) = (1.0, 2.0 * x);
+    if n == 0 {
+        return previous;
+    }
+    for k in 1..n {
+        let next = 2.0 * x * current - 2.0 * k as f64 * previous;
+        previous = current;
+        current = next;
+    }
+    current
+}
+
+/// Returns the associated Laguerre polynomial Lₙ^(α)(x).
+pub fn laguerre(n: usize, alpha: f64, x: f64) -> f64 {
+    let (mut previous, mut current) = (1.0, 1.0 + alpha - x);
+    if n == 0 {
+        return previous;
+    }
+    for k in 1..n {
+        let k = k as f64;
+        let next = ((2.0 * k + 1.0 + alpha - x) * current - (k + alpha) * previous) / (k + 1.0);
+        previous = current;
+        current = next;
+    }
+    current
+}
+
+/// Returns the associated Legendre function P_l^m(x) for |x| <= 1, including the
+/// Condon-Shortley phase (-1)^m. It is zero for m > l.
+pub fn legendre(l: usize, m: usize, x: f64) -> f64 {
+    if m > l {
+        return 0.0;
+    }
+
+    // P_m^m = (-1)^m (2m - 1)!! (1 - x²)^(m/2)
+    let sine = (1.0 - x * x).max(0.0).sqrt();
+    let mut previous = (1..=m).fold(1.0, |p, k| -p * (2 * k - 1) as f64 * sine);
+    if l == m {
+        return previous;
+    }
+
+    let mut current = x * (2 * m + 1) as f64 * previous;
+    for k in (m + 2)..=l {
+        let next =
+            (x * (2 * k - 1) as f64 * current - (k + m - 1) as f64 * previous) / (k - m) as f64;
+        previous = current;
+        current = next;
+    }
+    current
+}
+
+/// Returns the Gegenbauer polynomial Cₙ^(α)(x).
+pub fn gegenbauer(n: usize, alpha: f64, x: f64) -> f64 {
+    let (mut previous, mut current) = (1.0, 2.0 * alpha * x);
+    if n == 0 {
+        return previous;
+    }
+    for k in 1..n {
+        let k = k as f64;
+        let next =
+            (2.0 * x * (k + alpha) * current - (k + 2.0 * alpha - 1.0) * previous) / (k + 1.0);
+        previous = current;
+        current = next;
+    }
+    current
+}
+
+/// The Airy functions and their derivatives at a point.
+#[derive(Clone, Copy, Debug)]
+pub struct Airy {
+    pub ai: f64,
+    pub ai_prime: f64,
+    pub bi: f64,
+    pub bi_prime: f64,
+}
+
+/// Returns Ai(x), Bi(x) and their derivatives, the solutions of y'' = xy. Ai
+/// decays and Bi grows for x > 0, and both oscillate for x < 0.
+///
+/// The Maclaurin series is used for -7 < x < 5.5 and the asymptotic expansions
+/// outside of it. The relative error is near machine precision except around
+/// x = 5.5, where it rises to about 1e-8.
+pub fn airy(x: f64) -> Airy {
+    if x <= -7.0 || x >= 5.5 {
+        airy_asymptotic(x)
+    } else {
+        airy_series(x)
+    }
+}
+
+/// Ai(0) and -Ai'(0)
+const AIRY_C1: f64 = 0.355_028_053_887_817_2;
+const AIRY_C2: f64 = 0.258_819_403_792_806_8;
+
+/// Sums the two power series f and g with Ai = c₁f - c₂g and Bi = √3(c₁f + c₂g).
+fn airy_series(x: f64) -> Airy {
+    let x3 = x * x * x;
+    let (mut f, mut f_prime, mut g, mut g_prime) = (1.0, 0.0, x, 1.0);
+    let (mut f_term, mut g_term) = (1.0, x);
+
+    for k in 1..200 {
+        let k = k as f64;
+        let f_prime_term = f_term * x * x / (3.0 * k - 1.0);
+        let g_prime_term = g_term * x * x / (3.0 * k);
+        f_term *= x3 / ((3.0 * k - 1.0) * (3.0 * k));
+        g_term *= x3 / ((3.0 * k) * (3.0 * k + 1.0));
+
+        f += f_term;
+        f_prime += f_prime_term;
+        g += g_term;
+        g_prime += g_prime_term;
+
+        if [
+            (f_term, f),
+            (f_prime_term, f_prime),
+            (g_term, g),
+            (g_prime_term, g_prime),
+        ]
+        .iter()
+        .all(|(term, sum)| term.abs() <= f64::EPSILON * sum.abs())
+        {
+            break;
+        }
+    }
+
+    let sqrt_3 = 3.0_f64.sqrt();
+    Airy {
+        ai: AIRY_C1 * f - AIRY_C2 * g,
+        ai_prime: AIRY_C1 * f_prime - AIRY_C2 * g_prime,
+        bi: sqrt_3 * (AIRY_C1 * f + AIRY_C2 * g),
+        bi_prime: sqrt_3 * (AIRY_C1 * f_prime + AIRY_C2 * g_prime),
+    }
+}
+
+/// Sums the asymptotic expansions in ζ = (2/3)|x|^(3/2), truncated at their
+/// smallest term.
+fn airy_asymptotic(x: f64) -> Airy {
+    let zeta = 2.0 / 3.0 * x.abs().powf(1.5);
+
+    // The terms u_k/ζ^k and v_k/ζ^k of the expansions
+    let mut terms = Vec::new();
+    let mut u = 1.0;
+    for k in 0..40 {
+        if k > 0 {
+            let k = k as f64;
+            u *= (6.0 * k - 5.0) * (6.0 * k - 3.0) * (6.0 * k - 1.0)
+                / ((2.0 * k - 1.0) * 216.0 * k * zeta);
+        }
+        let v = -(6.0 * k as f64 + 1.0) / (6.0 * k as f64 - 1.0) * u;
+        if let Some((previous, _)) = terms.last() {
+            if u.abs() > f64::abs(*previous) || u.abs() < f64::EPSILON {
+                break;
+            }
+        }
+        terms.push((u, v));
+    }
+
+    let quarter = x.abs().powf(0.25);
+    let sqrt_pi = PI.sqrt();
+
+    if x > 0.0 {
+        let sum = |sign: f64| {
+            terms
+                .iter()
+                .enumerate()
+                .fold((0.0, 0.0), |(u_sum, v_sum), (k, (u, v))| {
+                    let sign = sign.powi(k as i32);
+                    (u_sum + sign * u, v_sum + sign * v)
+                })
+        };
+        let (u_decaying, v_decaying) = sum(-1.0);
+        let (u_growing, v_growing) = sum(1.0);
+        let (decay, growth) = ((-zeta).exp(), zeta.exp());
+
+        Airy {
+            ai: decay * u_decaying / (2.0 * sqrt_pi * quarter),
+            ai_prime: -quarter * decay * v_decaying / (2.0 * sqrt_pi),
+            bi: growth * u_growing / (sqrt_pi * quarter),
+            bi_prime: quarter * growth * v_growing / sqrt_pi,
+        }
+    } else {
+        // The even and odd terms, with alternating signs, multiply the sine and
+        // cosine of the phase
+        let (mut p, mut q, mut r, mut s) = (0.0, 0.0, 0.0, 0.0);
+        for (k, (u, v)) in terms.iter().enumerate() {
+            let sign = if (k / 2).is_multiple_of(2) { 1.0 } else { -1.0 };
+            if k.is_multiple_of(2) {
+                p += sign * u;
+                r += sign * v;
+            } else {
+                q += sign * u;
+                s += sign * v;
+            }
+        }
+        let (sine, cosine) = (zeta + 0.25 * PI).sin_cos();
+
+        Airy {
+            ai: (sine * p - cosine * q) / (sqrt_pi * quarter),
+            ai_prime: -quarter * (cosine * r + sine * s) / sqrt_pi,
+            bi: (cosine * p + sine * q) / (sqrt_pi * quarter),
+            bi_prime: quarter * (sine * r - cosine * s) / sqrt_pi,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::special::*;
+
+    fn close(a: f64, b: f64, tolerance: f64) -> bool {
+        (a - b).abs() <= tolerance * b.abs()
+    }
+
+    #[test]
+    fn orthogonal_polynomials() {
+        for x in [-0.9_f64, -0.3, 0.0, 0.4, 0.8] {
+            let sine = (1.0 - x * x).sqrt();
+            assert!(close(hermite(3, x), 8.0 * x * x * x - 12.0 * x, 1e-12));
+            assert!(close(
+                laguerre(2, 1.5, x),
+                0.5 * (x * x - 2.0 * 3.5 * x + 2.5 * 3.5),
+                1e-12
+            ));
+            assert!(close(legendre(2, 1, x), -3.0 * x * sine, 1e-12));
+            assert!(close(
+                legendre(3, 0, x),
+                0.5 * (5.0 * x * x * x - 3.0 * x),
+                1e-12
+            ));
+            assert!(close(legendre(3, 3, x), -15.0 * sine * sine * sine, 1e-12));
+            assert!(close(gegenbauer(2, 1.5, x), 7.5 * x * x - 1.5, 1e-12));
+        }
+        assert_eq!(legendre(1, 2, 0.5), 0.0);
+
+        assert!(close(gamma(5.0), 24.0, 1e-13));
+        assert!(close(gamma(0.5), PI.sqrt(), 1e-13));
+        assert!(close(gamma(-0.5), -2.0 * PI.sqrt(), 1e-13));
+        assert!(close(gamma(0.3), 2.991_568_987_687_591, 1e-13));
+        assert!(close(ln_gamma(50.5), 146.519_255_490_720_64, 1e-13));
+    }
+
+    #[test]
+    fn airy_functions() {
+        let expected = [
+            (
+                -10.0,
+                [
+                    0.040_241_238_486_443_19,
+                    0.996_265_044_132_79,
+                    -0.314_679_829_643_838_6,
+                    0.119_414_113_399_909_23,
+                ],
+            ),
+            (
+                -2.0,
+                [
+                    0.227_407_428_201_685_6,
+                    0.618_259_020_741_691,
+                    -0.412_302_587_956_398_5,
+                    0.278_795_166_921_169_5,
+                ],
+            ),
+            (
+                0.0,
+                [
+                    0.355_028_053_887_817_2,
+                    -0.258_819_403_792_806_8,
+                    0.614_926_627_446_000_7,
+                    0.448_288_357_353_826_4,
+                ],
+            ),
+            (
+                3.0,
+                [
+                    0.006_591_139_357_460_719,
+                    -0.011_912_976_705_951_317,
+                    14.037_328_963_730_232,
+                    22.922_214_966_382_17,
+                ],
+            ),
+            (
+                10.0,
+                [
+                    1.104_753_255_289_868_6e-10,
+                    -3.520_633_676_738_924e-10,
+                    455_641_153.548_225_16,
+                    1_429_236_134.482_865_8,
+                ],
+            ),
+        ];
+        for (x, [ai, ai_prime, bi, bi_prime]) in expected {
+            let airy = airy(x);
+            assert!(close(airy.ai, ai, 1e-10));
+            assert!(close(airy.ai_prime, ai_prime, 1e-10));
+            assert!(close(airy.bi, bi, 1e-10));
+            assert!(close(airy.bi_prime, bi_prime, 1e-10));
+        }
+
+        // The Wronskian Ai Bi' - Ai' Bi = 1/π holds on both sides of each switch
+        // between the series and the asymptotic expansions
+        for x in [-7.01, -6.99, 5.49, 5.51] {
+            let airy = airy(x);
+            let wronskian = airy.ai * airy.bi_prime - airy.ai_prime * airy.bi;
+            assert!(close(wronskian, PI.recip(), 1e-7));
+        }
+    }
+}
@@ -0,0 +1,91 @@
+/// Returns the arithmetic mean of the data.
+pub fn mean(data: &[f64]) -> f64 {
+    data.iter().sum::<f64>() / data.len() as f64
+}
+
+/// Estimates the standard error of the mean of correlated data, such as successive
+/// Monte Carlo samples, by averaging over blocks of `block_size` samples and
+/// treating the block averages as independent. Incomplete trailing blocks are
+/// dropped, and zero is returned if there are fewer than two blocks.
+pub fn blocking_error(data: &[f64], block_size: usize) -> f64 {
+    let block_means: Vec<f64> = data.chunks_exact(block_size.max(1)).map(mean).collect();
+    let blocks = block_means.len();
+    if blocks < 2 {
+        return 0.0;
+    }
+
+    let block_mean = mean(&block_means);
+    let variance = block_means
+        .iter()
+        .map(|val| (val - block_mean) * (val - block_mean))
+        .sum::<f64>()
+        / (blocks - 1) as f64;
+    (variance / blocks as f64).sqrt()
+}
+
+/// A histogram that samples can be added to incrementally, so that long runs do
+/// not need to keep every sample in memory. The bins have width `bin_width` and
+/// start at `x_min`.
+pub struct Histogram {
+    x_min: f64,
+    bin_width: f64,
+    counts: Vec<f64>,
+    samples: usize,
+}
+
+impl Histogram {
+    pub fn new(x_min: f64, bin_width: f64, bins: usize) -> Self {
+        Histogram {
+            x_min,
+            bin_width,
+            counts: vec![0.0; bins],
+            samples: 0,
+        }
+    }
+
+    /// Bins the samples. Samples outside the bins are counted in the
+    /// normalization but not binned.
+    pub fn add(&mut self, samples: &[f64]) {
+        for x in samples {
+            let bin = ((x - self.x_min) / self.bin_width).floor();
+            if bin >= 0.0 && (bin as usize) < self.counts.len() {
+                self.counts[bin as usize] += 1.0;
+            }
+        }
+        self.samples += samples.len();
+    }
+
+    /// Returns the probability density in each bin, normalized over all samples.
+    pub fn density(&self) -> Vec<f64> {
+        let scale = 1.0 / (self.samples as f64 * self.bin_width);
+        self.counts.iter().map(|count| count * scale).collect()
+    }
+}
+
+/// Bins the samples into `bins` bins of width `bin_width` starting at `x_min` and
+/// returns the probability density in each bin, normalized over all samples.
+/// Samples outside the bins are counted in the normalization but not binned.
+pub fn histogram(samples: &[f64], x_min: f64, bin_width: f64, bins: usize) -> Vec<f64> {
+    let mut histogram = Histogram::new(x_min, bin_width, bins);
+    histogram.add(samples);
+    histogram.density()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::statistics::*;
+
+    #[test]
+    fn statistics() {
+        let data = [1.0, 3.0, 2.0, 2.0, 5.0, 1.0];
+        assert_eq!(mean(&data), 14.0 / 6.0);
+
+        // Block means 2, 2 and 3
+        let expected = (1.0_f64 / 3.0 / 3.0).sqrt();
+        assert!((blocking_error(&data, 2) - expected).abs() < 1e-12);
+        assert_eq!(blocking_error(&data, 6), 0.0);
+
+        let density = histogram(&[0.1, 0.2, 0.6, 1.5, 7.0], 0.0, 0.5, 4);
+        assert_eq!(density, vec![0.8, 0.4, 0.0, 0.4]);
+    }
+}
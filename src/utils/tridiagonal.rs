@@ -0,0 +1,65 @@
+use std::ops::{Div, Mul, Sub};
+
+/// Solves the tridiagonal system A x = rhs with the Thomas algorithm. `lower[i]`
+/// and `upper[i]` are the entries left and right of `diagonal[i]`, so `lower[0]` and
+/// `upper[n - 1]` are ignored. No pivoting is done, so the matrix should be
+/// diagonally dominant.
+pub fn solve_tridiagonal<T>(lower: &[T], diagonal: &[T], upper: &[T], rhs: &[T]) -> Vec<T>
+where
+    T: Copy + Sub<Output = T> + Mul<Output = T> + Div<Output = T>,
+{
+    let n = diagonal.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    // Forward elimination, keeping the modified upper diagonal and right hand side
+    let mut upper_prime = Vec::with_capacity(n);
+    let mut rhs_prime = Vec::with_capacity(n);
+    upper_prime.push(upper[0] / diagonal[0]);
+    rhs_prime.push(rhs[0] / diagonal[0]);
+    for i in 1..n {
+        let denominator = diagonal[i] - lower[i] * upper_prime[i - 1];
+        upper_prime.push(upper[i] / denominator);
+        rhs_prime.push((rhs[i] - lower[i] * rhs_prime[i - 1]) / denominator);
+    }
+
+    // Back substitution
+    let mut x = rhs_prime;
+    for i in (0..n - 1).rev() {
+        x[i] = x[i] - upper_prime[i] * x[i + 1];
+    }
+
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::tridiagonal::solve_tridiagonal;
+
+    #[test]
+    fn solves_system() {
+        let lower = [0.0, 1.0, -2.0, 0.5, 1.5];
+        let diagonal = [4.0, 5.0, 6.0, 4.5, 3.0];
+        let upper = [1.0, -1.0, 2.0, 1.0, 0.0];
+        let expected = [1.0, -2.0, 3.0, 0.5, -1.0];
+
+        let rhs: Vec<f64> = (0..5)
+            .map(|i| {
+                let mut val = diagonal[i] * expected[i];
+                if i > 0 {
+                    val += lower[i] * expected[i - 1];
+                }
+                if i < 4 {
+                    val += upper[i] * expected[i + 1];
+                }
+                val
+            })
+            .collect();
+
+        let x = solve_tridiagonal(&lower, &diagonal, &upper, &rhs);
+        for (a, b) in x.iter().zip(expected.iter()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+}